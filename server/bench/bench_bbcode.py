@@ -0,0 +1,51 @@
+#!/usr/bin/env python3
+"""
+Benchmark for BBCode stripping.
+
+Measures strip_bbcode throughput on a short post without tags, a typical
+~1KB post and a ~5KB post with deeply nested tags.
+
+Usage:
+    python bench/bench_bbcode.py [--number 2000]
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import argparse
+import timeit
+
+from src.bbcode import strip_bbcode
+
+PLAIN = '今天的更新内容还挺多的，晚上回去再仔细看看吧。'
+
+TYPICAL = ('[quote][pid=123456,7890123,1]Reply[/pid] [b]Post by [uid=150058]某用户[/uid] '
+           '(2024-01-01 12:00):[/b]<br/>之前说的那个问题[/quote]'
+           '[b]更新说明[/b]<br/>[color=red]v1.2[/color] 修复了若干问题 [s:ac:哭笑]<br/>'
+           '[img]./mon_202401/01/-7Q2o-abcd.jpg[/img]<br/>'
+           '详情见 [url=https://bbs.nga.cn/read.php?tid=12345]这里[/url] &amp; 公告<br/>')
+TYPICAL = (TYPICAL * (1024 // len(TYPICAL.encode()) + 1))
+
+NESTED = ''.join(f'[{tag}]' for tag in ('b', 'i', 'u', 'color=red', 'size=120%', 'align=center') * 8)
+NESTED = (NESTED + '嵌套内容 text<br/>' + ''.join(
+    f'[/{tag.split("=")[0]}]' for tag in reversed(('b', 'i', 'u', 'color=red', 'size=120%', 'align=center') * 8)))
+NESTED = NESTED * (5 * 1024 // len(NESTED.encode()) + 1)
+
+CASES = [('plain 50B', PLAIN), ('typical 1KB', TYPICAL), ('nested 5KB', NESTED)]
+
+
+def main():
+    parser = argparse.ArgumentParser(description='Benchmark strip_bbcode')
+    parser.add_argument('--number', type=int, default=2000, help='Calls per measurement')
+    args = parser.parse_args()
+    
+    print(f"{'case':<12} {'bytes':>6} {'us/call':>8} {'MB/s':>7}")
+    for name, content in CASES:
+        size = len(content.encode())
+        best = min(timeit.repeat(lambda: strip_bbcode(content), number=args.number, repeat=5))
+        per_call = best / args.number
+        print(f"{name:<12} {size:>6} {per_call * 1e6:>8.1f} {size / per_call / 1e6:>7.1f}")
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,110 @@
+#!/usr/bin/env python3
+"""
+Benchmark for ThreadMonitor.check_thread without network access.
+
+The crawler is replaced by one that returns pre-built pages instantly, so the
+timings cover parsing, de-duplication against the database, BBCode stripping,
+filtering and notification building.
+
+Usage:
+    python bench/bench_monitor.py [--repeat 5]
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import argparse
+import json
+import statistics
+import tempfile
+import time
+
+from src.monitor import ThreadMonitor
+
+TID = 12345
+PER_PAGE = 20
+CONTENT = ('[quote][pid=1]Reply[/pid] [b]Someone[/b]: earlier post[/quote]'
+           '[b]更新[/b] [color=red]v1.2[/color]<br/>[img]./mon_202401/01/a.jpg[/img]'
+           '[url=https://example.com]link[/url] [s:ac:哭笑]<br/>') * 3
+
+
+def build_page(page_num, total_posts):
+    start = (page_num - 1) * PER_PAGE
+    return {
+        'code': 0, 'tsubject': 'Bench Thread', 'tauthor': 'User0', 'tauthorid': 100,
+        'vrows': total_posts, 'totalPage': (total_posts + PER_PAGE - 1) // PER_PAGE,
+        'currentPage': page_num, 'perPage': PER_PAGE,
+        'result': [{
+            'pid': 100000 + n, 'tid': TID, 'fid': 1,
+            'author': {'uid': 100 if n % 2 else 200, 'username': f'User{n}'},
+            'postdate': '2024-01-01 12:00', 'postdatetimestamp': 1704096000 + n,
+            'content': CONTENT, 'lou': n
+        } for n in range(start, min(start + PER_PAGE, total_posts))]
+    }
+
+
+class NullManager:
+    """Discards notifications."""
+    def send(self, title, message, **kwargs):
+        return 1
+
+
+def run_once(new_pages):
+    """Time one check_thread call that finds new_pages pages of new posts."""
+    tmp = tempfile.mkdtemp()
+    config_path = os.path.join(tmp, 'config.json')
+    with open(config_path, 'w', encoding='utf-8') as f:
+        json.dump({'ngaPassportUid': 'uid', 'ngaPassportCid': 'cid',
+                   'console_notification_enabled': False,
+                   'monitored_threads': [{'tid': TID, 'author_notification': [100],
+                                          'filter': {'exclude_keywords': ['广告']}}]}, f)
+    
+    monitor = ThreadMonitor(db_path=os.path.join(tmp, 'nga.db'), config_path=config_path)
+    monitor.notification_manager = NullManager()
+    state = {'total': PER_PAGE}
+    pages = {}
+    
+    def fetch_page(tid, page_num):
+        key = (state['total'], page_num)
+        if key not in pages:
+            pages[key] = build_page(page_num, state['total'])
+        return pages[key]
+    
+    monitor.crawler.fetch_page = fetch_page
+    monitor.add_thread(TID, author_notification=[100])
+    
+    # Pre-build the pages so only the monitor's own work is timed
+    state['total'] = PER_PAGE * (new_pages + 1)
+    for page_num in range(1, new_pages + 2):
+        fetch_page(TID, page_num)
+    
+    start = time.perf_counter()
+    result = monitor.check_thread(TID, verbose=False)
+    elapsed = time.perf_counter() - start
+    monitor.close()
+    assert 'error' not in result, result
+    return elapsed, result['total_new_posts']
+
+
+def main():
+    parser = argparse.ArgumentParser(description='Benchmark check_thread')
+    parser.add_argument('--repeat', type=int, default=5, help='Runs per page count')
+    args = parser.parse_args()
+    
+    print(f"{'pages':>6} {'posts':>6} {'median ms':>10} {'min ms':>8} {'ms/post':>8}")
+    for new_pages in (1, 5, 20):
+        timings = []
+        for _ in range(args.repeat):
+            sys.stdout = open(os.devnull, 'w')
+            try:
+                elapsed, posts = run_once(new_pages)
+            finally:
+                sys.stdout.close()
+                sys.stdout = sys.__stdout__
+            timings.append(elapsed * 1000)
+        median = statistics.median(timings)
+        print(f"{new_pages:>6} {posts:>6} {median:>10.1f} {min(timings):>8.1f} {median / posts:>8.2f}")
+
+
+if __name__ == '__main__':
+    main()
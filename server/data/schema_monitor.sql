@@ -9,7 +9,7 @@ CREATE TABLE IF NOT EXISTS monitored_threads (
     last_checked TIMESTAMP,
     last_post_timestamp INTEGER DEFAULT 0,
     is_active BOOLEAN DEFAULT 1,
-    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (datetime('now', 'localtime')),
     FOREIGN KEY (tid) REFERENCES threads(tid) ON DELETE CASCADE
 );
 
@@ -20,11 +20,33 @@ CREATE TABLE IF NOT EXISTS monitoring_events (
     event_type TEXT NOT NULL,  -- 'new_post', 'error', 'check'
     post_count INTEGER DEFAULT 0,
     message TEXT,
-    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMP DEFAULT (datetime('now', 'localtime')),
     FOREIGN KEY (tid) REFERENCES threads(tid) ON DELETE CASCADE
 );
 
+-- Sent notifications log
+CREATE TABLE IF NOT EXISTS notification_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tid INTEGER NOT NULL,
+    pid INTEGER,
+    author_uid INTEGER,
+    author_name TEXT,
+    title TEXT,
+    message TEXT,
+    url TEXT,
+    sent_at TIMESTAMP DEFAULT (datetime('now', 'localtime'))
+);
+
+-- Notifications up to last_history_id don't count towards a thread's total
+CREATE TABLE IF NOT EXISTS notification_stats_resets (
+    tid INTEGER PRIMARY KEY,
+    last_history_id INTEGER NOT NULL,
+    reset_at TIMESTAMP DEFAULT (datetime('now', 'localtime'))
+);
+
 -- Create indexes
 CREATE INDEX IF NOT EXISTS idx_monitored_active ON monitored_threads(is_active);
 CREATE INDEX IF NOT EXISTS idx_monitoring_events_tid ON monitoring_events(tid);
-CREATE INDEX IF NOT EXISTS idx_monitoring_events_created ON monitoring_events(created_at);
+CREATE INDEX IF NOT EXISTS idx_notification_history_tid ON notification_history(tid, sent_at);
+CREATE INDEX IF NOT EXISTS idx_notification_history_sent ON notification_history(sent_at);
+CREATE INDEX IF NOT EXISTS idx_notification_history_author ON notification_history(author_uid, sent_at);
@@ -4,12 +4,130 @@ Main entry point for NGA Reminder.
 """
 import sys
 import os
+import atexit
+import socket
+import ssl
 import argparse
 
 # Ensure src is in python path
 sys.path.insert(0, os.path.dirname(os.path.abspath(__file__)))
 
 
+def write_pid_file(pid_file):
+    """
+    Write the process ID to pid_file and remove it again on exit.
+    Exits if the file already holds the PID of a running process.
+    """
+    if os.path.exists(pid_file):
+        try:
+            with open(pid_file, 'r') as f:
+                existing_pid = int(f.read().strip())
+            os.kill(existing_pid, 0)
+        except ProcessLookupError:
+            print(f"Removing stale PID file {pid_file}")
+        except PermissionError:
+            # Process exists but belongs to another user
+            print(f"Error: Another instance is already running (PID {existing_pid})", file=sys.stderr)
+            sys.exit(1)
+        except (ValueError, OSError):
+            print(f"Warning: Ignoring unreadable PID file {pid_file}", file=sys.stderr)
+        else:
+            if existing_pid != os.getpid():
+                print(f"Error: Another instance is already running (PID {existing_pid})", file=sys.stderr)
+                sys.exit(1)
+
+    with open(pid_file, 'w') as f:
+        f.write(str(os.getpid()))
+
+    def remove_pid_file():
+        try:
+            with open(pid_file, 'r') as f:
+                if f.read().strip() == str(os.getpid()):
+                    os.remove(pid_file)
+        except OSError:
+            pass
+
+    atexit.register(remove_pid_file)
+
+
+def bind_unix_socket(socket_path, mode):
+    """
+    Bind a Unix domain socket for the API server, replacing a stale socket file.
+
+    Args:
+        socket_path: Filesystem path of the socket
+        mode: Permission bits for the socket file, e.g. 0o660
+
+    Returns:
+        Bound socket
+    """
+    if os.path.exists(socket_path):
+        os.remove(socket_path)
+
+    sock = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    # Owner-only until chmod, so other users can't connect in between
+    old_umask = os.umask(0o177)
+    try:
+        sock.bind(socket_path)
+    finally:
+        os.umask(old_umask)
+    os.chmod(socket_path, mode)
+
+    def remove_socket():
+        if os.path.exists(socket_path):
+            os.remove(socket_path)
+
+    atexit.register(remove_socket)
+    return sock
+
+
+def tls_options(config):
+    """
+    Build uvicorn TLS arguments from the server_tls_* and server_mtls_* settings.
+
+    Returns:
+        Keyword arguments for uvicorn (empty if TLS is not configured)
+    """
+    if not config.get('server_tls_certfile'):
+        return {}
+
+    options = {
+        'ssl_certfile': config['server_tls_certfile'],
+        'ssl_keyfile': config.get('server_tls_keyfile')
+    }
+
+    # The handshake verifies certificates that are presented, but the path isn't known yet:
+    # src.api requires one (server_mtls_require_client_cert) for everything but /health and /live.
+    if config.get('server_mtls_ca_certs'):
+        from src.mtls import ClientCertProtocol
+        options['ssl_ca_certs'] = config['server_mtls_ca_certs']
+        options['ssl_cert_reqs'] = ssl.CERT_OPTIONAL
+        options['http'] = ClientCertProtocol
+
+    return options
+
+
+def print_banner(config_path, config, bind):
+    """Print version, config file, threads, notifiers and bind address before the server starts."""
+    from src import __version__
+    from src.notification import SENDER_NAMES
+
+    threads = config.get('monitored_threads') or []
+    enabled = sum(1 for t in threads if t.get('enabled', True))
+    # Console is on unless disabled, the others are off unless enabled
+    enabled_keys = {'console': 'console_notification_enabled'}
+    notifiers = [name for name in SENDER_NAMES
+                 if config.get(enabled_keys.get(name, f'{name}_enabled'), name == 'console')]
+    print('=' * 80)
+    print(f"NGA Reminder {__version__}")
+    print('=' * 80)
+    print(f"Config file: {config_path}")
+    print(f"Threads:     {len(threads)} ({enabled} enabled)")
+    print(f"Notifiers:   {', '.join(notifiers) or 'none'}")
+    print(f"Listening:   {bind}")
+    print('=' * 80)
+
+
 def main():
     """Main entry point with command routing."""
     parser = argparse.ArgumentParser(
@@ -17,6 +135,12 @@ def main():
         formatter_class=argparse.RawDescriptionHelpFormatter
     )
 
+    parser.add_argument('--print-schema', action='store_true',
+                        help='Print the JSON Schema of config.json and exit')
+    parser.add_argument('--migrate', nargs='?', const='', metavar='CONFIG',
+                        help='Upgrade the config file (default: $NGA_CONFIG_FILE or config/config.json) '
+                             'to the current schema version, print the changes and exit')
+
     subparsers = parser.add_subparsers(dest='mode', help='Operation mode')
 
     # CLI mode (existing monitor commands)
@@ -27,9 +151,31 @@ def main():
     server_parser.add_argument('--host', type=str, default='127.0.0.1', help='Host to bind to')
     server_parser.add_argument('--port', type=int, default=8000, help='Port to bind to')
     server_parser.add_argument('--reload', action='store_true', help='Enable auto-reload')
+    server_parser.add_argument('--config', type=str,
+                               help='Config file path (default: $NGA_CONFIG_FILE or config/config.json)')
 
     args, remaining = parser.parse_known_args()
 
+    if args.print_schema:
+        import json
+        from src.config_schema import CONFIG_SCHEMA
+        print(json.dumps(CONFIG_SCHEMA, indent=2, ensure_ascii=False))
+        return
+
+    if args.migrate is not None:
+        import json
+        from src.config import DEFAULT_CONFIG_PATH, migrate_config_file
+        from src.config_diff import describe_change
+        config_path = args.migrate or DEFAULT_CONFIG_PATH
+        try:
+            changes = migrate_config_file(config_path)
+        except (OSError, json.JSONDecodeError) as e:
+            print(f"Error: Could not migrate {config_path}: {e}", file=sys.stderr)
+            sys.exit(1)
+        for change in changes:
+            print(f"  {describe_change(change)}")
+        return
+
     if args.mode == 'server':
         # Run FastAPI server
         try:
@@ -39,17 +185,42 @@ def main():
             print("Error: uvicorn not installed. Run: pip install -r requirements.txt", file=sys.stderr)
             sys.exit(1)
 
-        # Read server config from config file
-        config_path = 'config/config.json'
+        # Read server config from config file: --config, then NGA_CONFIG_FILE, then the default
+        if args.config:
+            config_path, source = args.config, '--config'
+        elif os.environ.get('NGA_CONFIG_FILE'):
+            config_path, source = os.environ['NGA_CONFIG_FILE'], 'NGA_CONFIG_FILE'
+        else:
+            config_path, source = 'config/config.json', 'default'
+        # The API module (and --reload workers) read the path from the environment
+        os.environ['NGA_CONFIG_FILE'] = config_path
+        print(f"Using config file {config_path} ({source})")
+        try:
+            from src.config import create_config_from_env
+            from src.config_migrations import log_schema_version
+            create_config_from_env(config_path)
+        except (ValueError, OSError) as e:
+            print(f"Error: {e}", file=sys.stderr)
+            sys.exit(1)
         default_host = '127.0.0.1'
         default_port = 8000
+        pid_file = None
+        socket_path = None
+        socket_mode = '660'
+        tls = {}
+        config = {}
 
         if os.path.exists(config_path):
             try:
                 with open(config_path, 'r', encoding='utf-8') as f:
                     config = json.load(f)
+                    log_schema_version(config)
                     default_host = config.get('server_host', default_host)
                     default_port = config.get('server_port', default_port)
+                    pid_file = config.get('pid_file')
+                    socket_path = config.get('server_socket_path')
+                    socket_mode = config.get('server_socket_mode', socket_mode)
+                    tls = tls_options(config)
             except Exception as e:
                 print(f"Warning: Could not read config file: {e}", file=sys.stderr)
 
@@ -57,14 +228,31 @@ def main():
         host = args.host if args.host != '127.0.0.1' else default_host
         port = args.port if args.port != 8000 else default_port
 
-        print(f"Starting NGA Reminder API Server on {host}:{port}")
-        print(f"API Documentation: http://{host}:{port}/docs")
+        if pid_file:
+            write_pid_file(pid_file)
+
+        if socket_path:
+            # Serve on a Unix domain socket instead of TCP, e.g. behind nginx in the same pod
+            if args.reload:
+                print("Warning: --reload is not supported with server_socket_path, ignoring", file=sys.stderr)
+            sock = bind_unix_socket(socket_path, int(str(socket_mode), 8))
+            print_banner(config_path, config, f"unix:{socket_path}")
+            server = uvicorn.Server(uvicorn.Config("src.api:app", **tls))
+            server.run(sockets=[sock])
+            return
+
+        scheme = 'https' if tls else 'http'
+        print_banner(config_path, config, f"{scheme}://{host}:{port}")
+        print(f"API Documentation: {scheme}://{host}:{port}/docs")
+        if tls.get('ssl_ca_certs') and config.get('server_mtls_require_client_cert', True):
+            print("Client certificates required (mTLS), except for /health and /live")
 
         uvicorn.run(
             "src.api:app",
             host=host,
             port=port,
-            reload=args.reload
+            reload=args.reload,
+            **tls
         )
     else:
         # Default to CLI mode (existing monitor functionality)
@@ -3,15 +3,43 @@
 FastAPI server for NGA Reminder.
 Provides REST API for querying posts with background monitoring.
 """
-from fastapi import FastAPI, Query, HTTPException
-from fastapi.responses import JSONResponse
+from fastapi import FastAPI, Query, HTTPException, Header, Body, Request
+from fastapi.middleware.gzip import GZipMiddleware
+from fastapi.openapi.docs import get_swagger_ui_html
+from fastapi.responses import JSONResponse, Response, HTMLResponse
+from pydantic import BaseModel, Field
 from typing import Optional, List, Dict, Any
+import ipaddress
+import json
+import os
+import secrets
+import signal
+import sys
 import threading
 import time
+from collections import deque
 from contextlib import asynccontextmanager
+from datetime import datetime
 
-from .monitor import ThreadMonitor
-from .database import NGADatabase
+from .monitor import ThreadMonitor, next_check_time
+from .database import NGADatabase, merge_pages, parse_page_result
+from .config import (
+    DEFAULT_CONFIG_PATH, find_monitored_thread, load_config, save_config, validate_config,
+    redact_config, restore_redacted, upsert_monitored_thread, remove_monitored_thread,
+    set_thread_enabled, add_save_listener, remove_save_listener, is_config_writable,
+    describe_config_changes, trim_inert_threads
+)
+from . import __version__
+from .config_diff import diff_config
+from .config_migrations import migrate_config
+from .nga_crawler import CrawlerError, CrawlerTimeout, HttpError, NgaApiError, NGACrawler, thread_metadata
+from .mtls import common_name
+from .notification import SENDER_NAMES
+from .observability import capture_exception, install_thread_excepthook
+from .util import content_preview
+
+# ../static/dashboard.html relative to api.py
+DASHBOARD_PATH = os.path.join(os.path.dirname(os.path.dirname(__file__)), 'static', 'dashboard.html')
 
 
 # Global monitor instance and stop event
@@ -19,6 +47,33 @@ monitor: Optional[ThreadMonitor] = None
 monitor_thread: Optional[threading.Thread] = None
 monitor_stop_event: Optional[threading.Event] = None
 
+# Set after the monitor loop's first cycle, reported by /ready
+monitor_ready_event = threading.Event()
+
+# Set to make the monitor reload config.json before its next cycle
+monitor_reload_event = threading.Event()
+
+# Serializes read-modify-write cycles on config.json
+config_lock = threading.Lock()
+
+# Seconds between checks that config.json is still writable
+CONFIG_WRITABLE_CHECK_INTERVAL = 300
+
+# Result of the last writability check, reported by /ready (None until the first check)
+config_writable: Optional[bool] = None
+
+
+def _read_server_config() -> Dict[str, Any]:
+    """Read config.json for settings applied when the app is created."""
+    try:
+        return load_config(DEFAULT_CONFIG_PATH)
+    except (OSError, json.JSONDecodeError) as e:
+        print(f"Warning: Could not read config for server settings: {e}")
+        return {}
+
+
+server_config = _read_server_config()
+
 
 @asynccontextmanager
 async def lifespan(app: FastAPI):
@@ -37,6 +92,18 @@ async def lifespan(app: FastAPI):
         try:
             # Create ThreadMonitor instance inside the thread to avoid SQLite threading issues
             monitor = ThreadMonitor()
+            # Config changes made through the API apply without waiting for a reload
+            add_save_listener(monitor.on_config_saved)
+            
+            # Early feedback on NGA being unreachable or the cookies being wrong.
+            # Startup continues either way, credentials may still be added through the API.
+            threads = monitor.config.get('monitored_threads') or []
+            if threads:
+                try:
+                    monitor.crawler.test_connection(threads[0]['tid'])
+                    print("✓ NGA API reachable")
+                except CrawlerError as e:
+                    print(f"Warning: NGA connection test failed: {e}")
             
             # Sync monitored threads from config file
             print("Syncing monitored threads from config...")
@@ -47,15 +114,42 @@ async def lifespan(app: FastAPI):
                 print(f"Synced {sync_result.get('added', 0) + sync_result.get('updated', 0)} thread(s)")
             
             # Run with a 30-second check interval (default from monitor.py)
-            monitor.run_loop(check_all_interval=30, stop_event=monitor_stop_event)
+            monitor.run_loop(check_all_interval=30, stop_event=monitor_stop_event,
+                             ready_event=monitor_ready_event, reload_event=monitor_reload_event)
         except Exception as e:
-            print(f"Monitor error: {e}")
+            print(f"Error: Monitor crashed: {e}")
             import traceback
             traceback.print_exc()
+            capture_exception(e)
+            # Serving the API without a monitor hides the failure; exit so the supervisor restarts us
+            sys.stdout.flush()
+            sys.stderr.flush()
+            os._exit(1)
     
-    monitor_thread = threading.Thread(target=run_monitor, daemon=True)
+    install_thread_excepthook()
+    monitor_thread = threading.Thread(target=run_monitor, name='monitor', daemon=True)
     monitor_thread.start()
     
+    def check_config_writable():
+        """Warn early if config.json can no longer be written, before an API change is lost."""
+        global config_writable
+        while True:
+            config_writable = is_config_writable(DEFAULT_CONFIG_PATH)
+            if not config_writable:
+                print(f"Error: {DEFAULT_CONFIG_PATH} is not writable, config changes will fail")
+            if monitor_stop_event.wait(CONFIG_WRITABLE_CHECK_INTERVAL):
+                break
+    
+    threading.Thread(target=check_config_writable, daemon=True).start()
+    
+    # SIGHUP reloads config.json (kill -HUP <pid>)
+    if hasattr(signal, 'SIGHUP'):
+        try:
+            signal.signal(signal.SIGHUP, lambda signum, frame: monitor_reload_event.set())
+        except ValueError:
+            # Not running in the main thread
+            pass
+    
     # Wait a bit for monitor to initialize
     time.sleep(0.5)
     print("Monitor started.")
@@ -64,6 +158,8 @@ async def lifespan(app: FastAPI):
     
     # Shutdown: Signal monitor to stop
     print("Shutting down monitor...")
+    if monitor:
+        remove_save_listener(monitor.on_config_saved)
     if monitor_stop_event:
         monitor_stop_event.set()
     
@@ -79,24 +175,222 @@ async def lifespan(app: FastAPI):
 app = FastAPI(
     title="NGA Reminder API",
     description="REST API for querying NGA forum posts with background monitoring",
-    version="1.0.0",
+    version=__version__,
     lifespan=lifespan
 )
 
 
-@app.get("/")
-async def root():
-    """Root endpoint."""
+@app.exception_handler(CrawlerError)
+async def crawler_error_handler(request: Request, exc: CrawlerError) -> JSONResponse:
+    """
+    Answer requests that failed fetching from NGA, see _crawler_error_status.
+    """
+    return JSONResponse(status_code=_crawler_error_status(exc),
+                        content={"detail": str(exc), "error_kind": exc.kind})
+
+
+def _crawler_error_status(exc: CrawlerError) -> int:
+    """HTTP errors keep NGA's status, timeouts are 504, unknown threads 404 and anything else 502."""
+    if isinstance(exc, HttpError):
+        return exc.status
+    if isinstance(exc, CrawlerTimeout):
+        return 504
+    if isinstance(exc, NgaApiError) and exc.code == NgaApiError.THREAD_NOT_FOUND:
+        return 404
+    return 502
+
+
+class BodySizeLimitMiddleware:
+    """
+    Reject request bodies larger than max_bytes with 413.
+    Checks Content-Length up front and counts chunked bodies as they are received.
+    """
+    
+    def __init__(self, app, max_bytes: int):
+        self.app = app
+        self.max_bytes = max_bytes
+    
+    async def __call__(self, scope, receive, send):
+        if scope['type'] != 'http':
+            await self.app(scope, receive, send)
+            return
+        
+        content_length = dict(scope['headers']).get(b'content-length')
+        if content_length and content_length.isdigit() and int(content_length) > self.max_bytes:
+            await self._reject(scope, receive, send)
+            return
+        
+        received = 0
+        
+        async def limited_receive():
+            nonlocal received
+            message = await receive()
+            if message['type'] == 'http.request':
+                received += len(message.get('body', b''))
+                if received > self.max_bytes:
+                    # HTTPException so FastAPI's body parsing passes it through as a 413
+                    raise HTTPException(status_code=413, detail="Request body too large")
+            return message
+        
+        await self.app(scope, limited_receive, send)
+    
+    async def _reject(self, scope, receive, send):
+        response = JSONResponse(status_code=413, content={
+            "detail": f"Request body too large (limit {self.max_bytes} bytes)"
+        })
+        await response(scope, receive, send)
+
+
+def _parse_allowed_ips(entries: Optional[List[str]]) -> Optional[List[Any]]:
+    """Parse server_allowed_ips into networks. None means every client is allowed."""
+    if entries is None:
+        return None
+    
+    networks = []
+    for entry in entries:
+        try:
+            networks.append(ipaddress.ip_network(entry, strict=False))
+        except ValueError:
+            print(f"Warning: Ignoring invalid server_allowed_ips entry: {entry}")
+    return networks
+
+
+allowed_networks = _parse_allowed_ips(server_config.get('server_allowed_ips'))
+
+# Probes used by load balancers and orchestrators are reachable from any address
+IP_FILTER_EXEMPT_PATHS = {'/health', '/live', '/ready'}
+
+
+@app.middleware("http")
+async def ip_allowlist(request: Request, call_next):
+    """Reject clients outside server_allowed_ips with 403."""
+    # request.client is None for Unix socket connections, which are guarded by file permissions
+    if allowed_networks is None or request.url.path in IP_FILTER_EXEMPT_PATHS or request.client is None:
+        return await call_next(request)
+    
+    try:
+        client_ip = ipaddress.ip_address(request.client.host)
+    except ValueError:
+        client_ip = None
+    
+    if client_ip is None or not any(client_ip in network for network in allowed_networks):
+        return JSONResponse(status_code=403, content={"detail": "Client address not allowed"})
+    
+    return await call_next(request)
+
+
+# Probes can't always present a client certificate
+MTLS_EXEMPT_PATHS = {'/health', '/live'}
+
+mtls_required = bool(server_config.get('server_tls_certfile') and server_config.get('server_mtls_ca_certs')
+                     and server_config.get('server_mtls_require_client_cert', True))
+
+
+@app.middleware("http")
+async def client_cert_check(request: Request, call_next):
+    """
+    Log the client certificate CN of each mTLS request, and reject requests without
+    a certificate with 403 when server_mtls_require_client_cert is on.
+    """
+    # Set by src.mtls.ClientCertProtocol; the TLS handshake already verified the certificate
+    tls = request.scope.get('extensions', {}).get('tls')
+    cn = common_name(tls.get('client_cert_name')) if tls else None
+    if tls is not None:
+        print(f"mTLS client CN={cn or '-'}: {request.method} {request.url.path}")
+    if mtls_required and not (tls and tls.get('client_cert_name')) and request.url.path not in MTLS_EXEMPT_PATHS:
+        return JSONResponse(status_code=403, content={"detail": "Client certificate required"})
+    return await call_next(request)
+
+
+app.add_middleware(BodySizeLimitMiddleware,
+                   max_bytes=server_config.get('server_max_request_body_bytes', 1024 * 1024))
+
+# Gzip responses for clients that send Accept-Encoding: gzip
+if server_config.get('server_compression', True):
+    app.add_middleware(GZipMiddleware, minimum_size=1000)
+
+
+def _check_api_key(config: Dict[str, Any], api_key: Optional[str], required: bool = False):
+    """
+    Verify the X-API-Key header against the configured api_key.
+    
+    Args:
+        config: Current configuration
+        api_key: Value of the X-API-Key header
+        required: Reject the request if no api_key is configured
+        
+    Raises:
+        HTTPException: 403 if the key is missing or wrong
+    """
+    expected = config.get('api_key')
+    if not expected:
+        if required:
+            raise HTTPException(status_code=403, detail="api_key must be configured for this request")
+        return
+    if not api_key or not secrets.compare_digest(api_key, expected):
+        raise HTTPException(status_code=403, detail="Invalid or missing X-API-Key header")
+
+
+def _request_monitor() -> ThreadMonitor:
+    """
+    The background monitor, sharing its config, crawler, senders and thread states,
+    with a database connection of its own: SQLite connections only work in the thread
+    that opened them. Close it when done.
+    
+    Raises:
+        HTTPException: 503 before the monitor has started
+    """
+    if not monitor:
+        raise HTTPException(status_code=503, detail="Monitor not initialized")
+    return monitor.with_own_connection()
+
+
+def _load_current_config() -> Dict[str, Any]:
+    """Load config.json or fail the request."""
+    try:
+        return load_config(DEFAULT_CONFIG_PATH)
+    except (OSError, json.JSONDecodeError) as e:
+        raise HTTPException(status_code=500, detail=f"Could not read config: {e}")
+
+
+@app.get("/", response_class=HTMLResponse)
+def root() -> str:
+    """Serve the admin dashboard; the API index is at /api."""
+    return dashboard()
+
+
+@app.get("/api")
+async def api_versions():
+    """List the available API versions and the main endpoints."""
     return {
         "message": "NGA Reminder API",
-        "version": "1.0.0",
+        "version": __version__,
+        "versions": ["v1"],
+        "current": "v1",
         "endpoints": {
             "posts": "/api/v1/posts",
+            "threads": "/api/v1/threads",
+            "notifications": "/api/v1/notifications",
+            "dashboard": "/",
             "docs": "/docs"
         }
     }
 
 
+# FastAPI serves the generated spec at /openapi.json and Swagger UI at /docs;
+# these aliases keep them next to the API routes.
+@app.get("/api/openapi.json", include_in_schema=False)
+async def api_openapi_spec():
+    """OpenAPI specification of the API."""
+    return app.openapi()
+
+
+@app.get("/api/docs", include_in_schema=False)
+async def api_docs():
+    """Swagger UI for the API."""
+    return get_swagger_ui_html(openapi_url="/api/openapi.json", title=f"{app.title} - Docs")
+
+
 @app.get("/api/v1/posts")
 async def get_posts(
     tid: int = Query(..., description="Thread ID"),
@@ -124,22 +418,725 @@ async def get_posts(
         raise HTTPException(status_code=500, detail=f"Database error: {str(e)}")
 
 
+def _thread_status(tid: int) -> Dict[str, Any]:
+    """Runtime status of a thread from the background monitor."""
+    state = monitor.thread_states.get(tid) if monitor else None
+    snooze = {"snooze_until": state.snooze_until.isoformat() if monitor and monitor.is_snoozed(tid) else None}
+    if not state or not state.last_checked_at:
+        return {"status": "pending", "consecutive_errors": 0, "last_error": None, **snooze}
+    
+    return {
+        "status": "error" if state.consecutive_errors else "ok",
+        "consecutive_errors": state.consecutive_errors,
+        "last_error": state.last_error,
+        **snooze
+    }
+
+
+def thread_summary(row: Dict[str, Any], options: Dict[str, Any],
+                   last_seen_post_number: Optional[int], total_notifications: int,
+                   next_check: Optional[datetime] = None) -> Dict[str, Any]:
+    """
+    The fields of a monitored thread that GET /api/v1/threads returns. Built from an
+    explicit list so new database columns and config fields stay internal until added here.
+    
+    Args:
+        row: Row from ThreadMonitor.list_monitored()
+        options: The thread's entry in config.json
+        last_seen_post_number: Highest stored post number, None if no posts are stored
+        total_notifications: Notifications sent for the thread in total
+        next_check: next_check_time() of the thread
+    """
+    return {
+        'tid': row['tid'],
+        'enabled': options.get('enabled', True),
+        'note': options.get('note'),
+        'title': row.get('title'),
+        'author_name': row.get('author_name'),
+        'total_posts': row.get('total_posts'),
+        'last_seen_post_number': last_seen_post_number,
+        'check_interval': row.get('check_interval'),
+        'last_checked': row.get('last_checked'),
+        'next_check_at': next_check.isoformat() if next_check else None,
+        'on_demand': row.get('check_interval') == 0,
+        'author_notification_count': len(options.get('author_notification') or []),
+        'created_at': options.get('created_at'),
+        'updated_at': options.get('updated_at'),
+        'total_notifications_all_time': total_notifications
+    }
+
+
 @app.get("/api/v1/threads")
-async def list_monitored_threads() -> List[Dict[str, Any]]:
+def list_monitored_threads() -> List[Dict[str, Any]]:
     """
     Get list of currently monitored threads.
     
     Returns:
-        List of monitored threads with their configuration
+        List of thread summaries (see thread_summary) with their runtime status
+    """
+    threads = []
+    db_monitor = _request_monitor()
+    try:
+        counts = db_monitor.get_notification_counts()
+        for row in db_monitor.list_monitored():
+            options = find_monitored_thread(db_monitor.config, row['tid']) or {}
+            next_check = next_check_time(row['last_checked'], row['check_interval'],
+                                         monitor.thread_states.get(row['tid']))
+            thread = thread_summary(row, options, db_monitor._max_post_number(row['tid']),
+                                    counts.get(row['tid'], 0), next_check)
+            thread.update(_thread_status(row['tid']))
+            threads.append(thread)
+    except Exception as e:
+        raise HTTPException(status_code=500, detail=f"Error: {str(e)}")
+    finally:
+        db_monitor.close()
+    
+    return threads
+
+
+@app.get("/api/v1/threads/{tid}")
+def get_monitored_thread(tid: int) -> Dict[str, Any]:
+    """
+    Get the configuration and runtime state of a single monitored thread.
+    
+    Returns:
+        The thread's entry from config.json merged with its runtime status
+    """
+    thread = find_monitored_thread(_load_current_config(), tid)
+    if thread is None:
+        raise HTTPException(status_code=404, detail=f"Thread {tid} not monitored")
+    
+    db_monitor = _request_monitor()
+    try:
+        row = next((t for t in db_monitor.list_monitored() if t['tid'] == tid), None)
+        all_time = db_monitor.get_notification_counts().get(tid, 0)
+    except Exception as e:
+        raise HTTPException(status_code=500, detail=f"Error: {str(e)}")
+    finally:
+        db_monitor.close()
+    
+    result = dict(thread)
+    result.update(_thread_status(tid))
+    state = monitor.thread_states.get(tid) if monitor else None
+    next_check = next_check_time(row['last_checked'], row['check_interval'], state) if row else None
+    result.update({
+        "last_checked_at": state.last_checked_at.isoformat() if state and state.last_checked_at else None,
+        "no_new_posts_streak": state.no_new_posts_streak if state else 0,
+        "total_notifications": state.total_notifications if state else 0,
+        "total_notifications_all_time": all_time,
+        "effective_interval": state.effective_interval if state else None,
+        "next_check_at": next_check.isoformat() if next_check else None,
+        "on_demand": bool(row) and row['check_interval'] == 0
+    })
+    return result
+
+
+class AddThreadRequest(BaseModel):
+    """Request body for adding a monitored thread."""
+    tid: int
+    author_filter: Optional[List[int]] = None
+    author_notification: Optional[List[int]] = None
+    check_interval: int = 300
+    note: Optional[str] = None
+
+
+@app.post("/api/v1/threads", status_code=202)
+def add_monitored_thread(
+    request: AddThreadRequest,
+    x_api_key: Optional[str] = Header(None)
+) -> Dict[str, Any]:
+    """
+    Add a thread to config.json and start monitoring it.
+    The initial fetch of all pages runs in the background.
+    
+    Returns:
+        Accepted status, or 422 if the thread doesn't exist or isn't accessible
+    """
+    _check_api_key(_load_current_config(), x_api_key)
+    # Other CrawlerErrors (NGA unreachable, ...) are answered by crawler_error_handler
+    if not NGACrawler(DEFAULT_CONFIG_PATH).thread_exists(request.tid):
+        raise HTTPException(status_code=422,
+                            detail=f"Thread {request.tid} does not exist or is not accessible")
+    
+    entry = {
+        'tid': request.tid,
+        'author_filter': request.author_filter,
+        'author_notification': request.author_notification,
+        'check_interval': request.check_interval,
+        'enabled': True
+    }
+    if request.note:
+        entry['note'] = request.note
+    
+    with config_lock:
+        config = _load_current_config()
+        _check_api_key(config, x_api_key)
+        upsert_monitored_thread(config, entry)
+        errors = validate_config(config)
+        if errors:
+            raise HTTPException(status_code=422, detail=errors)
+        save_config(config, DEFAULT_CONFIG_PATH)
+    
+    def run_add():
+        add_monitor = _request_monitor()
+        try:
+            add_monitor.add_thread(request.tid, request.author_filter, request.check_interval,
+                                   author_notification=request.author_notification,
+                                   stop_event=monitor_stop_event)
+        finally:
+            add_monitor.close()
+    
+    threading.Thread(target=run_add, daemon=True).start()
+    return {"status": "adding", "tid": request.tid}
+
+
+@app.delete("/api/v1/threads/{tid}")
+def remove_monitored_thread_endpoint(tid: int, x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Remove a thread from config.json and stop monitoring it.
+    Stored posts are kept.
+    
+    Returns:
+        Removal status
+    """
+    with config_lock:
+        config = _load_current_config()
+        _check_api_key(config, x_api_key)
+        if remove_monitored_thread(config, tid):
+            save_config(config, DEFAULT_CONFIG_PATH)
+    
+    db_monitor = _request_monitor()
+    try:
+        db_monitor.remove_thread(tid)
+    finally:
+        db_monitor.close()
+    
+    return {"status": "removed", "tid": tid}
+
+
+@app.post("/api/v1/threads/trim")
+def trim_threads(x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Remove threads from config.json that can never trigger a notification (no
+    author_notification and no notification_rules) and stop monitoring them.
+    Stored posts are kept. Requires the API key even if none is configured.
+    
+    Returns:
+        TIDs of the removed threads
+    """
+    with config_lock:
+        config = _load_current_config()
+        _check_api_key(config, x_api_key, required=True)
+        removed = trim_inert_threads(config)
+        if removed:
+            save_config(config, DEFAULT_CONFIG_PATH)
+    
+    db_monitor = _request_monitor()
+    try:
+        for tid in removed:
+            db_monitor.remove_thread(tid)
+    finally:
+        db_monitor.close()
+    
+    return {"status": "trimmed", "removed_threads": removed}
+
+
+def _set_enabled(tid: int, enabled: bool, x_api_key: Optional[str]) -> Dict[str, Any]:
+    """Flip a thread's enabled flag in config.json and apply it to the monitor."""
+    with config_lock:
+        config = _load_current_config()
+        _check_api_key(config, x_api_key)
+        if set_thread_enabled(config, tid, enabled) is None:
+            raise HTTPException(status_code=404, detail=f"Thread {tid} not monitored")
+        save_config(config, DEFAULT_CONFIG_PATH)
+    
+    if not enabled:
+        db_monitor = _request_monitor()
+        try:
+            db_monitor.remove_thread(tid)
+        finally:
+            db_monitor.close()
+        return {"status": "disabled", "tid": tid}
+    
+    # Resuming may need to fetch the posts added while disabled
+    def run_enable():
+        enable_monitor = _request_monitor()
+        try:
+            enable_monitor.load_from_config(stop_event=monitor_stop_event, tids={tid})
+        finally:
+            enable_monitor.close()
+    
+    threading.Thread(target=run_enable, daemon=True).start()
+    return {"status": "enabled", "tid": tid}
+
+
+@app.post("/api/v1/threads/{tid}/enable")
+def enable_monitored_thread(tid: int, x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Resume monitoring a thread by setting enabled to true in config.json.
+    The thread is synced in the background.
+    
+    Returns:
+        Enabled status
+    """
+    return _set_enabled(tid, True, x_api_key)
+
+
+@app.post("/api/v1/threads/{tid}/disable")
+def disable_monitored_thread(tid: int, x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Pause monitoring a thread by setting enabled to false in config.json,
+    keeping its configuration and stored posts.
+    
+    Returns:
+        Disabled status
+    """
+    return _set_enabled(tid, False, x_api_key)
+
+
+class SnoozeRequest(BaseModel):
+    """Request body for snoozing a thread."""
+    duration_secs: int = Field(..., gt=0)
+
+
+@app.post("/api/v1/threads/{tid}/snooze")
+def snooze_thread(tid: int, request: SnoozeRequest, x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Skip scheduled checks of a thread for duration_secs. Checking resumes
+    automatically afterwards. The snooze is not kept across restarts.
+    
+    Returns:
+        End of the snooze
+    """
+    config = _load_current_config()
+    _check_api_key(config, x_api_key)
+    if find_monitored_thread(config, tid) is None:
+        raise HTTPException(status_code=404, detail=f"Thread {tid} not monitored")
+    if not monitor:
+        raise HTTPException(status_code=503, detail="Monitor not initialized")
+    
+    snooze_until = monitor.snooze_thread(tid, request.duration_secs)
+    return {"status": "snoozed", "tid": tid, "snooze_until": snooze_until.isoformat()}
+
+
+@app.delete("/api/v1/threads/{tid}/snooze")
+def cancel_thread_snooze(tid: int, x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    End a thread's snooze early; it is checked again on its normal schedule.
+    
+    Returns:
+        Cancelled status
     """
+    _check_api_key(_load_current_config(), x_api_key)
     if not monitor:
         raise HTTPException(status_code=503, detail="Monitor not initialized")
+    if not monitor.cancel_snooze(tid):
+        raise HTTPException(status_code=404, detail=f"Thread {tid} is not snoozed")
+    return {"status": "resumed", "tid": tid}
+
+
+class ResetLastSeenRequest(BaseModel):
+    """Request body for replaying a thread's notifications."""
+    post_number: int = Field(0, ge=0)
+
+
+@app.post("/api/v1/threads/{tid}/reset_last_seen")
+def reset_last_seen(
+    tid: int,
+    request: Optional[ResetLastSeenRequest] = None,
+    x_api_key: Optional[str] = Header(None)
+) -> Dict[str, Any]:
+    """
+    Replay a thread's notifications: posts after post_number (default 0) are
+    fetched again on the next check and notified as if they were new.
+    
+    Returns:
+        Number of stored posts removed and the thread's reset post count
+    """
+    config = _load_current_config()
+    _check_api_key(config, x_api_key)
+    if find_monitored_thread(config, tid) is None:
+        raise HTTPException(status_code=404, detail=f"Thread {tid} not monitored")
     
+    post_number = request.post_number if request else 0
+    db_monitor = _request_monitor()
     try:
-        threads = monitor.list_monitored()
-        return threads
+        result = db_monitor.reset_last_seen(tid, post_number)
     except Exception as e:
-        raise HTTPException(status_code=500, detail=f"Error: {str(e)}")
+        raise HTTPException(status_code=500, detail=f"Database error: {str(e)}")
+    finally:
+        db_monitor.close()
+    
+    if result is None:
+        raise HTTPException(status_code=404, detail=f"Thread {tid} not in database")
+    return result
+
+
+@app.post("/api/v1/threads/{tid}/reset_stats")
+def reset_thread_stats(
+    tid: int,
+    confirm: bool = Query(False, description="Must be true; guards against accidental resets"),
+    x_api_key: Optional[str] = Header(None)
+) -> Dict[str, Any]:
+    """
+    Restart a thread's notification counts from zero.
+    The notification history is kept.
+    
+    Returns:
+        Reset status, or 400 without confirm=true
+    """
+    config = _load_current_config()
+    _check_api_key(config, x_api_key)
+    if find_monitored_thread(config, tid) is None:
+        raise HTTPException(status_code=404, detail=f"Thread {tid} not monitored")
+    if not confirm:
+        raise HTTPException(status_code=400, detail="Pass confirm=true to reset the thread's stats")
+    
+    db_monitor = _request_monitor()
+    try:
+        db_monitor.reset_notification_count(tid)
+    except Exception as e:
+        raise HTTPException(status_code=500, detail=f"Database error: {str(e)}")
+    finally:
+        db_monitor.close()
+    
+    state = monitor.thread_states.get(tid) if monitor else None
+    if state:
+        state.total_notifications = 0
+    return {"status": "reset", "tid": tid}
+
+
+@app.get("/api/v1/threads/{tid}/history")
+def get_thread_check_history(tid: int) -> List[Dict[str, Any]]:
+    """
+    Get the most recent check results of a monitored thread, newest first.
+    Kept in memory; the history starts empty when the server restarts.
+    
+    Returns:
+        List of check history entries
+    """
+    if not monitor:
+        raise HTTPException(status_code=503, detail="Monitor not initialized")
+    
+    state = monitor.thread_states.get(tid)
+    if not state:
+        return []
+    
+    return [
+        {
+            "checked_at": entry.checked_at.isoformat(),
+            "posts_found": entry.posts_found,
+            "new_notifications_sent": entry.new_notifications_sent,
+            "error": entry.error,
+            "duration_ms": entry.duration_ms,
+            "check_id": entry.check_id
+        }
+        for entry in reversed(list(state.history))
+    ]
+
+
+@app.post("/api/v1/threads/{tid}/check")
+def check_thread_now(tid: int, x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Check a monitored thread for new posts immediately.
+    Notifications are sent as in a scheduled check.
+    
+    Returns:
+        Check summary
+    """
+    _check_api_key(_load_current_config(), x_api_key)
+    
+    check_monitor = _request_monitor()
+    try:
+        row = next((t for t in check_monitor.list_monitored() if t['tid'] == tid), None)
+        if not row:
+            raise HTTPException(status_code=404, detail=f"Thread {tid} not monitored")
+        result = check_monitor.check_thread(tid, verbose=False, monitor_config=row)
+    finally:
+        check_monitor.close()
+    
+    if monitor:
+        monitor.update_thread_state(tid, result, row['check_interval'])
+    
+    if 'error' in result:
+        if result.get('error_kind') and check_monitor.crawler.last_error:
+            raise check_monitor.crawler.last_error
+        raise HTTPException(status_code=502, detail=result['error'])
+    
+    return {
+        "tid": tid,
+        "new_posts": result['new_posts'],
+        "total_posts": result['total_posts'],
+        "notifications_sent": result['notifications_sent']
+    }
+
+
+@app.get("/api/v1/threads/{tid}/posts")
+def preview_recent_posts(
+    tid: int,
+    limit: int = Query(5, ge=1, le=50, description="Number of most recent posts"),
+    x_api_key: Optional[str] = Header(None)
+) -> List[Dict[str, Any]]:
+    """
+    Fetch the most recent posts of a thread from NGA, for debugging missing notifications.
+    Nothing is stored and the monitor's state is not changed.
+    
+    Returns:
+        Up to limit posts, oldest first, with content_preview instead of the full content
+    """
+    _check_api_key(_load_current_config(), x_api_key)
+    
+    crawler = NGACrawler(DEFAULT_CONFIG_PATH)
+    # CrawlerErrors are answered by crawler_error_handler
+    first_page = crawler.fetch_page_or_raise(tid, 1)
+    
+    # Walk back from the last page until there are enough posts
+    pages = []
+    page_num = first_page.get('totalPage', 1)
+    while page_num >= 1 and sum(len(p.get('result') or []) for p in pages) < limit:
+        page_result = first_page if page_num == 1 else crawler.fetch_page_or_raise(tid, page_num)
+        pages.append(page_result)
+        page_num -= 1
+    
+    return [{
+        "pid": post['pid'],
+        "post_number": post['post_number'],
+        "author_name": post['author_name'],
+        "author_uid": post['author_uid'],
+        "content_preview": content_preview(post['content']),
+        "post_date": post['post_date']
+    } for post in merge_pages(pages)[-limit:]]
+
+
+# Requests per minute to /api/v1/crawl/test, which bypasses the monitor's schedule
+CRAWL_TEST_RATE_LIMIT = 10
+_crawl_test_times: deque = deque()
+_crawl_test_lock = threading.Lock()
+
+
+@app.get("/api/v1/crawl/test")
+def crawl_test(
+    tid: int = Query(..., description="Thread ID"),
+    page: int = Query(1, ge=1, description="Page number"),
+    include_raw: bool = Query(False, description="Also return NGA's unprocessed response body"),
+    x_api_key: Optional[str] = Header(None)
+):
+    """
+    Fetch one page from NGA and return what the crawler makes of it, for debugging
+    NGA access, credentials or API format changes. Nothing is stored.
+    Requires the API key and is limited to CRAWL_TEST_RATE_LIMIT requests per minute.
+    
+    Returns:
+        Thread metadata and parsed posts, or the crawler error with the status
+        of crawler_error_handler; with include_raw also the response body as raw
+    """
+    _check_api_key(_load_current_config(), x_api_key, required=True)
+    with _crawl_test_lock:
+        now = time.monotonic()
+        while _crawl_test_times and now - _crawl_test_times[0] > 60:
+            _crawl_test_times.popleft()
+        if len(_crawl_test_times) >= CRAWL_TEST_RATE_LIMIT:
+            raise HTTPException(status_code=429,
+                                detail=f"At most {CRAWL_TEST_RATE_LIMIT} crawl tests per minute")
+        _crawl_test_times.append(now)
+    
+    crawler = NGACrawler(DEFAULT_CONFIG_PATH)
+    try:
+        result = crawler.fetch_page_or_raise(tid, page)
+    except CrawlerError as e:
+        raw = {"raw": crawler.last_response_text} if include_raw else {}
+        return JSONResponse(status_code=_crawler_error_status(e),
+                            content={"detail": str(e), "error_kind": e.kind, **raw})
+    
+    _, posts = parse_page_result(result)
+    raw = {"raw": crawler.last_response_text} if include_raw else {}
+    return {"tid": tid, "page": page, "thread": thread_metadata(result), "posts": posts, **raw}
+
+
+@app.get("/api/v1/notifications")
+def get_notification_history(
+    limit: int = Query(50, ge=1, le=500, description="Maximum number of entries"),
+    offset: int = Query(0, ge=0, description="Number of entries to skip"),
+    tid: Optional[int] = Query(None, description="Filter by thread ID"),
+    author: Optional[str] = Query(None, description="Filter by author UID or exact author name"),
+    x_api_key: Optional[str] = Header(None)
+) -> Dict[str, Any]:
+    """
+    Get sent notifications, newest first.
+    
+    Returns:
+        Total number of matching notifications and the requested page
+    """
+    _check_api_key(_load_current_config(), x_api_key)
+    db_monitor = _request_monitor()
+    try:
+        return db_monitor.get_notification_history(limit, offset, tid, author)
+    except Exception as e:
+        raise HTTPException(status_code=500, detail=f"Database error: {str(e)}")
+    finally:
+        db_monitor.close()
+
+
+@app.get("/api/v1/notifiers")
+def list_notifiers() -> List[Dict[str, Any]]:
+    """
+    Get the notification senders and their delivery health.
+    
+    Returns:
+        One entry per sender (bark, pushbullet, slack, console) with enabled,
+        active, consecutive_failures, last_success_at and last_failure_at.
+        Counters restart when the config is reloaded.
+    """
+    if not monitor:
+        raise HTTPException(status_code=503, detail="Monitor not initialized")
+    return monitor.notification_manager.sender_status()
+
+
+@app.get("/dashboard", response_class=HTMLResponse)
+def dashboard() -> str:
+    """Serve the admin dashboard, also at /."""
+    with open(DASHBOARD_PATH, 'r', encoding='utf-8') as f:
+        return f.read()
+
+
+class NotificationPreviewRequest(BaseModel):
+    """Request body for rendering a notification preview."""
+    tid: int
+    sample_post_number: int
+
+
+@app.post("/api/v1/notifications/preview")
+def preview_notification(
+    request: NotificationPreviewRequest,
+    x_api_key: Optional[str] = Header(None)
+) -> Dict[str, Any]:
+    """
+    Render the notification for a post without sending it.
+    Fetches the post from NGA with the server's credentials if it isn't stored.
+    
+    Args:
+        request: Thread ID and post number of the sample post
+        
+    Returns:
+        Rendered title, message and url, plus whether the thread's
+        notification filter would send it
+    """
+    _check_api_key(_load_current_config(), x_api_key)
+    preview_monitor = _request_monitor()
+    try:
+        result = preview_monitor.preview_notification(request.tid, request.sample_post_number)
+    finally:
+        preview_monitor.close()
+    
+    if 'error' in result:
+        raise HTTPException(status_code=404, detail=result['error'])
+    return result
+
+
+@app.get("/api/v1/config/export")
+def export_config(
+    include_credentials: bool = Query(False, description="Include NGA/Bark credentials"),
+    x_api_key: Optional[str] = Header(None)
+):
+    """
+    Download the current configuration as a JSON file for backup, the
+    counterpart of POST /api/v1/config/import. Always needs the API key, so
+    api_key must be configured. Credentials are redacted unless include_credentials=true is passed.
+    
+    Returns:
+        Pretty-printed config.json as an attachment named nga_config_backup_<timestamp>.json
+    """
+    config = _load_current_config()
+    _check_api_key(config, x_api_key, required=True)
+    if not include_credentials:
+        config = redact_config(config)
+    
+    filename = f"nga_config_backup_{datetime.now().strftime('%Y%m%d_%H%M%S')}.json"
+    return Response(
+        content=json.dumps(config, indent=4, ensure_ascii=False),
+        media_type='application/json',
+        headers={'Content-Disposition': f'attachment; filename="{filename}"'}
+    )
+
+
+@app.post("/api/v1/config/import")
+def import_config(
+    config: Dict[str, Any] = Body(..., description="Complete configuration"),
+    x_api_key: Optional[str] = Header(None)
+) -> Dict[str, Any]:
+    """
+    Replace the configuration with the uploaded one.
+    Redacted credential placeholders keep their current values.
+    The file is only written if the new configuration validates. Saving applies the
+    settings to the monitor; added and removed threads are synced before its next cycle.
+    
+    Returns:
+        Changes like POST /api/v1/config/reload, or 422 with the validation errors
+    """
+    with config_lock:
+        current = _load_current_config()
+        _check_api_key(current, x_api_key)
+        
+        config = restore_redacted(config, current)
+        if isinstance(config, dict):
+            migrate_config(config)
+        errors = validate_config(config)
+        if errors:
+            raise HTTPException(status_code=422, detail=errors)
+        
+        try:
+            save_config(config, DEFAULT_CONFIG_PATH)
+        except OSError as e:
+            raise HTTPException(status_code=500, detail=f"Could not write config: {e}")
+    
+    # save_config listeners apply the settings; the reload also syncs thread rows
+    monitor_reload_event.set()
+    return {
+        "status": "imported",
+        "monitored_threads": len(config.get('monitored_threads', [])),
+        **_reload_summary(current, config)
+    }
+
+
+def _reload_summary(old: Dict[str, Any], new: Dict[str, Any]) -> Dict[str, Any]:
+    """Threads added and removed, senders whose settings changed and every changed field between two configs."""
+    old_tids = {t.get('tid') for t in old.get('monitored_threads', [])}
+    new_tids = {t.get('tid') for t in new.get('monitored_threads', [])}
+    changed_keys = {change['path'].split('.')[0].split('[')[0] for change in diff_config(old, new)}
+    return {
+        "added_threads": sorted(new_tids - old_tids),
+        "removed_threads": sorted(old_tids - new_tids),
+        "changed_notifiers": [name for name in SENDER_NAMES
+                              if any(key.startswith(f'{name}_') for key in changed_keys)],
+        "changes": describe_config_changes(old, new)
+    }
+
+
+@app.post("/api/v1/config/reload")
+def reload_config_endpoint(x_api_key: Optional[str] = Header(None)) -> Dict[str, Any]:
+    """
+    Reload config.json into the running monitor, like SIGHUP.
+    The monitor applies it before its next cycle.
+    
+    Returns:
+        Threads added and removed, notifiers whose settings changed and a
+        description of each changed field (credentials redacted), or 500 if the config cannot be read or is invalid
+    """
+    if not monitor:
+        raise HTTPException(status_code=503, detail="Monitor not initialized")
+    
+    config = _load_current_config()
+    _check_api_key(config, x_api_key)
+    
+    errors = validate_config(config)
+    if errors:
+        raise HTTPException(status_code=500, detail={"error": "Invalid config", "errors": errors})
+    
+    summary = _reload_summary(monitor.config, config)
+    monitor_reload_event.set()
+    print(f"Config reload requested via API: {len(summary['added_threads'])} thread(s) added, "
+          f"{len(summary['removed_threads'])} removed")
+    return {"status": "reloading", **summary}
 
 
 @app.get("/health")
@@ -149,3 +1146,28 @@ async def health_check():
         "status": "healthy",
         "monitor_running": monitor is not None and monitor_thread is not None and monitor_thread.is_alive()
     }
+
+
+@app.get("/live")
+async def liveness_probe():
+    """Liveness probe: the process is up and serving requests."""
+    return {"status": "alive"}
+
+
+@app.get("/ready")
+async def readiness_probe():
+    """
+    Readiness probe: the monitor is running and has completed its first cycle.
+    config_writable is informational and doesn't affect the status.
+    """
+    monitor_running = monitor_thread is not None and monitor_thread.is_alive()
+    ready = monitor_running and monitor_ready_event.is_set()
+    return JSONResponse(
+        status_code=200 if ready else 503,
+        content={
+            "status": "ready" if ready else "not ready",
+            "monitor_running": monitor_running,
+            "first_cycle_complete": monitor_ready_event.is_set(),
+            "config_writable": config_writable
+        }
+    )
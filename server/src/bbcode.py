@@ -0,0 +1,111 @@
+#!/usr/bin/env python3
+"""
+BBCode processing for NGA post content.
+Turns raw post content into plain text for notifications.
+"""
+
+import html
+import re
+
+SPOILER_PLACEHOLDER = '[spoiler]'
+
+_BR_RE = re.compile(r'<br\s*/?>', re.IGNORECASE)
+_SPOILER_RE = re.compile(r'\[s\](.*?)\[/s\]', re.IGNORECASE | re.DOTALL)
+_QUOTE_RE = re.compile(r'\[quote\].*?\[/quote\]', re.IGNORECASE | re.DOTALL)
+_IMG_RE = re.compile(r'\[img\].*?\[/img\]', re.IGNORECASE | re.DOTALL)
+_URL_RE = re.compile(r'\[url=([^\]]*)\](.*?)\[/url\]', re.IGNORECASE | re.DOTALL)
+# NGA emoticons, e.g. [s:ac:哭笑]
+_EMOTICON_RE = re.compile(r'\[s:[^\]]*\]', re.IGNORECASE)
+# Any remaining [tag], [tag=value] or [/tag]
+_TAG_RE = re.compile(r'\[/?[a-z]+(?:=[^\]]*)?\]', re.IGNORECASE)
+_BLANK_LINES_RE = re.compile(r'\n\s*\n+')
+_SPACES_RE = re.compile(r'[ \t]{2,}')
+_CODE_RE = re.compile(r'\[code(?:=[^\]]*)?\](.*?)\[/code\]', re.IGNORECASE | re.DOTALL)
+_FENCE_RE = re.compile(r'(```\n.*?\n```)', re.DOTALL)
+
+
+def _sub_closed(pattern: re.Pattern, repl, text: str, close_tag: str) -> str:
+    """
+    pattern.sub for [tag]...[/tag] patterns, limited to the text up to the last
+    close_tag. Opening tags after it can never match, and scanning each of them
+    to the end of the text makes the substitution quadratic on unclosed tags.
+    """
+    last = None
+    for last in re.finditer(re.escape(close_tag), text, re.IGNORECASE):
+        pass
+    if last is None:
+        return text
+    return pattern.sub(repl, text[:last.end()]) + text[last.end():]
+
+
+def strip_bbcode(content: str) -> str:
+    """
+    Convert NGA post content to plain text.
+
+    Line breaks become newlines, quoted replies are dropped, images become
+    "[image]", links keep their text, emoticons and all other tags are removed,
+    and HTML entities are decoded. SPOILER_PLACEHOLDER and Markdown code fences
+    produced by format_code_blocks are kept as they are.
+
+    Args:
+        content: Raw post content
+
+    Returns:
+        Plain text
+    """
+    parts = _FENCE_RE.split(content)
+    # Odd indexes are code fences
+    text = ''.join(part if i % 2 else _strip_text(part) for i, part in enumerate(parts))
+    text = _BLANK_LINES_RE.sub('\n', text)
+    return text.strip()
+
+
+def _strip_text(content: str) -> str:
+    """Strip BBCode from content without code fences."""
+    text = _BR_RE.sub('\n', content)
+    text = _sub_closed(_QUOTE_RE, '', text, '[/quote]')
+    text = _sub_closed(_IMG_RE, '[image]', text, '[/img]')
+    text = _sub_closed(_URL_RE, lambda m: m.group(2) or m.group(1), text, '[/url]')
+    text = _EMOTICON_RE.sub('', text)
+    text = _TAG_RE.sub(lambda m: m.group(0) if m.group(0) in (SPOILER_PLACEHOLDER, '[image]') else '', text)
+    text = html.unescape(text)
+    return _SPACES_RE.sub(' ', text)
+
+
+def format_code_blocks(content: str, max_lines: int, markdown: bool) -> str:
+    """
+    Format [code]...[/code] blocks for notifications.
+
+    Args:
+        content: Raw post content
+        max_lines: Maximum number of code lines kept per block (Markdown only)
+        markdown: Render blocks as Markdown code fences; otherwise replace each
+            block with "[code snippet N lines]"
+
+    Returns:
+        Content with code blocks replaced
+    """
+    def replace(match):
+        code = html.unescape(_BR_RE.sub('\n', match.group(1))).strip('\n')
+        lines = code.split('\n')
+        if not markdown:
+            return f"\n[code snippet {len(lines)} line{'s' if len(lines) != 1 else ''}]\n"
+        kept = lines[:max_lines]
+        if len(lines) > max_lines:
+            kept.append(f'... ({len(lines) - max_lines} more lines)')
+        return '\n```\n' + '\n'.join(kept) + '\n```\n'
+
+    return _sub_closed(_CODE_RE, replace, content, '[/code]')
+
+
+def redact_spoilers(content: str) -> str:
+    """
+    Replace [s]...[/s] spoiler blocks with SPOILER_PLACEHOLDER.
+
+    Args:
+        content: Raw post content
+
+    Returns:
+        Content with spoiler text removed
+    """
+    return _sub_closed(_SPOILER_RE, SPOILER_PLACEHOLDER, content, '[/s]')
@@ -0,0 +1,680 @@
+#!/usr/bin/env python3
+"""
+Configuration file helpers for NGA Reminder.
+Loading, validating, redacting and writing config.json.
+"""
+
+import copy
+import difflib
+import ipaddress
+import json
+import os
+import re
+import shutil
+from datetime import datetime, timezone
+from urllib.parse import parse_qs, urlsplit
+from typing import Callable, Dict, Any, List, Optional, Tuple
+
+from .config_diff import describe_change, diff_config
+from .config_migrations import CURRENT_SCHEMA_VERSION, migrate_config
+from .config_schema import CONFIG_SCHEMA, THREAD_SCHEMA
+from .i18n import STRINGS
+from .notification import SENDER_NAMES, is_valid_bark_url_template
+from .post_filter import validate_notification_rules, validate_post_filter
+from .schedule import validate_schedule, validate_timezone
+
+# Environment variable naming the config file, overridden by a --config argument
+CONFIG_FILE_ENV_VAR = 'NGA_CONFIG_FILE'
+
+DEFAULT_CONFIG_PATH = os.environ.get(CONFIG_FILE_ENV_VAR) or 'config/config.json'
+
+REDACTED = '[redacted]'
+
+# Top-level fields holding secrets that must not leave the server by default
+CREDENTIAL_FIELDS = ['ngaPassportUid', 'ngaPassportCid', 'bark_device_key', 'pushbullet_api_token',
+                     'slack_webhook_url', 'api_key', 'sentry_dsn']
+
+# Environment variables that override NGA credentials from the config file
+CREDENTIAL_ENV_VARS = {'ngaPassportUid': 'NGA_UID', 'ngaPassportCid': 'NGA_CID'}
+
+# Environment variables that make up a config when there is no config file (containers).
+# NGA_THREADS and NGA_MONITOR_INTERVAL are handled separately by config_from_env.
+CONFIG_ENV_VARS = {
+    'NGA_CRAWLER_API_URL': 'api_url',
+    'NGA_WEB_HOST': 'server_host',
+    'NGA_WEB_PORT': 'server_port',
+    'NGA_NOTIFIER_BARK_URL': 'bark_server_url',
+    'NGA_NOTIFIER_BARK_KEY': 'bark_device_key'
+}
+
+# Secret fields of top-level objects, e.g. proxy.password
+NESTED_CREDENTIAL_FIELDS = [('proxy', 'password')]
+
+# Called as listener(config, config_path) after save_config writes a file
+_save_listeners: List[Callable[[Dict[str, Any], str], None]] = []
+
+
+def add_save_listener(listener: Callable[[Dict[str, Any], str], None]):
+    """Register a callback run after every successful save_config, e.g. to apply API changes."""
+    _save_listeners.append(listener)
+
+
+def remove_save_listener(listener: Callable[[Dict[str, Any], str], None]):
+    """Unregister a callback added with add_save_listener."""
+    if listener in _save_listeners:
+        _save_listeners.remove(listener)
+
+
+def load_config(config_path: str = DEFAULT_CONFIG_PATH, migrate: bool = True) -> Dict[str, Any]:
+    """
+    Load configuration from a JSON file.
+    Configs with an older schema_version are migrated and written back, and
+    thread entries given as a url get the tid from it.
+
+    Args:
+        config_path: Path to config file
+        migrate: Apply config migrations and resolve thread urls
+
+    Returns:
+        Configuration dictionary
+
+    Raises:
+        FileNotFoundError: If the config file does not exist
+        json.JSONDecodeError: If the file is not valid JSON
+    """
+    with open(config_path, 'r', encoding='utf-8') as f:
+        config = json.load(f)
+
+    if migrate and isinstance(config, dict) and migrate_config(config):
+        try:
+            save_config(config, config_path, changed_by='startup')
+        except OSError as e:
+            print(f"Warning: Could not write migrated config {config_path}: {e}")
+    if migrate and isinstance(config, dict):
+        resolve_thread_urls(config)
+    return config
+
+
+def tid_from_url(url: Any) -> Optional[int]:
+    """
+    Thread ID in an NGA thread URL copied from the browser, e.g.
+    https://nga.178.com/read.php?tid=12345678
+
+    Returns:
+        The tid, or None if the URL has no positive tid parameter
+    """
+    if not isinstance(url, str):
+        return None
+    values = parse_qs(urlsplit(url).query).get('tid')
+    if not values or not values[0].isdigit() or int(values[0]) == 0:
+        return None
+    return int(values[0])
+
+
+def resolve_thread_urls(config: Dict[str, Any]):
+    """
+    Replace url with the tid it contains in monitored_threads entries that
+    give a url instead of a tid. Entries with both or with a URL without a tid
+    are left for validate_config to report.
+    """
+    threads = config.get('monitored_threads')
+    if not isinstance(threads, list):
+        return
+    for thread in threads:
+        if isinstance(thread, dict) and 'url' in thread and 'tid' not in thread:
+            tid = tid_from_url(thread['url'])
+            if tid is not None:
+                del thread['url']
+                thread['tid'] = tid
+
+
+def migrate_config_file(config_path: str = DEFAULT_CONFIG_PATH) -> List[Dict[str, Any]]:
+    """
+    Upgrade a config file to the current schema_version and write it back.
+    Running it again on an upgraded file changes nothing.
+
+    Returns:
+        Changes made, as returned by diff_config (empty if already up to date)
+
+    Raises:
+        OSError, json.JSONDecodeError: If the file can't be read or written
+    """
+    config = load_config(config_path, migrate=False)
+    original = copy.deepcopy(config)
+    if not migrate_config(config):
+        return []
+    save_config(config, config_path, changed_by='migrate')
+    return diff_config(original, config)
+
+
+def config_from_env(environ=os.environ) -> Optional[Dict[str, Any]]:
+    """
+    Build a config from CONFIG_ENV_VARS, NGA_MONITOR_INTERVAL (default check
+    interval) and NGA_THREADS (JSON array of TIDs or thread objects).
+    Credentials stay in NGA_UID/NGA_CID and are not copied into the config.
+
+    Returns:
+        None if none of the variables are set
+
+    Raises:
+        ValueError: If a variable can't be parsed
+    """
+    if not any(environ.get(var) for var in [*CONFIG_ENV_VARS, 'NGA_MONITOR_INTERVAL', 'NGA_THREADS']):
+        return None
+
+    config: Dict[str, Any] = {'schema_version': CURRENT_SCHEMA_VERSION}
+    for var, field in CONFIG_ENV_VARS.items():
+        if environ.get(var):
+            config[field] = environ[var]
+    if 'server_port' in config:
+        config['server_port'] = _env_int(environ, 'NGA_WEB_PORT')
+    if config.get('bark_device_key'):
+        config['bark_enabled'] = True
+
+    try:
+        threads = json.loads(environ.get('NGA_THREADS') or '[]')
+    except json.JSONDecodeError as e:
+        raise ValueError(f'NGA_THREADS: not valid JSON ({e})')
+    if not isinstance(threads, list):
+        raise ValueError('NGA_THREADS: must be a JSON array')
+
+    interval = _env_int(environ, 'NGA_MONITOR_INTERVAL') if environ.get('NGA_MONITOR_INTERVAL') else None
+    config['monitored_threads'] = []
+    for thread in threads:
+        entry = dict(thread) if isinstance(thread, dict) else {'tid': thread}
+        if interval is not None:
+            entry.setdefault('check_interval', interval)
+        config['monitored_threads'].append(entry)
+    return config
+
+
+def create_config_from_env(config_path: str = DEFAULT_CONFIG_PATH) -> bool:
+    """
+    Write config_path from environment variables (see config_from_env) if the
+    file doesn't exist yet. Later runs use the file, so API changes persist.
+
+    Returns:
+        True if the file was created
+
+    Raises:
+        ValueError: If the variables don't make up a valid config
+    """
+    if os.path.exists(config_path):
+        return False
+    config = config_from_env()
+    if config is None:
+        return False
+
+    errors = validate_config(config)
+    if errors:
+        raise ValueError('Invalid config from environment: ' + '; '.join(errors))
+    if os.path.dirname(config_path):
+        os.makedirs(os.path.dirname(config_path), exist_ok=True)
+    save_config(config, config_path, changed_by='env')
+    print(f"Created {config_path} from environment variables")
+    return True
+
+
+def _env_int(environ, var: str) -> int:
+    try:
+        return int(environ[var])
+    except ValueError:
+        raise ValueError(f'{var}: must be an integer')
+
+
+def save_config(config: Dict[str, Any], config_path: str = DEFAULT_CONFIG_PATH,
+                changed_by: str = 'api'):
+    """
+    Write configuration to a JSON file.
+    The existing file is first copied to <config_path>.bak unless
+    config_backup_on_write is false. If config_audit_log is set, the changes
+    are appended to that file as a JSON line. Listeners registered with
+    add_save_listener are called afterwards.
+
+    Args:
+        config: Configuration dictionary
+        config_path: Path to config file
+        changed_by: Origin of the change recorded in the audit log (api, signal, startup, env, migrate)
+    """
+    previous = None
+    if os.path.exists(config_path):
+        try:
+            previous = load_config(config_path, migrate=False)
+        except (OSError, json.JSONDecodeError):
+            previous = None
+
+        if config.get('config_backup_on_write', True):
+            try:
+                shutil.copy2(config_path, config_path + '.bak')
+            except OSError as e:
+                print(f"Warning: Could not back up {config_path}: {e}")
+
+    with open(config_path, 'w', encoding='utf-8') as f:
+        json.dump(config, f, indent=4, ensure_ascii=False)
+        f.write('\n')
+
+    if config.get('config_audit_log'):
+        _append_audit_entry(config['config_audit_log'], previous or {}, config, changed_by)
+
+    for listener in list(_save_listeners):
+        listener(copy.deepcopy(config), config_path)
+
+
+def is_config_writable(config_path: str = DEFAULT_CONFIG_PATH) -> bool:
+    """
+    Whether config_path can still be opened for writing, e.g. the volume it is
+    on hasn't become read-only. The file is not truncated or modified.
+    """
+    try:
+        with open(config_path, 'r+', encoding='utf-8'):
+            return True
+    except OSError:
+        return False
+
+
+def _append_audit_entry(audit_path: str, previous: Dict[str, Any], config: Dict[str, Any],
+                        changed_by: str):
+    """Append a config change to the audit log. Credentials are redacted."""
+    entry = {
+        'timestamp': datetime.now().astimezone().isoformat(timespec='seconds'),
+        'changed_by': changed_by,
+        'diff': redacted_diff(previous, config)
+    }
+    try:
+        with open(audit_path, 'a', encoding='utf-8') as f:
+            f.write(json.dumps(entry, ensure_ascii=False) + '\n')
+    except OSError as e:
+        print(f"Warning: Could not write config audit log {audit_path}: {e}")
+
+
+def redacted_diff(previous: Dict[str, Any], config: Dict[str, Any]) -> List[Dict[str, Any]]:
+    """diff_config of two configs with credential values replaced by [redacted]."""
+    diff = diff_config(previous, config)
+    nested_paths = {f'{parent}.{field}' for parent, field in NESTED_CREDENTIAL_FIELDS}
+    for change in diff:
+        if change['path'] in CREDENTIAL_FIELDS or change['path'] in nested_paths:
+            change['old'] = REDACTED if change['old'] else change['old']
+            change['new'] = REDACTED if change['new'] else change['new']
+        elif any(change['path'] == parent for parent, _ in NESTED_CREDENTIAL_FIELDS):
+            # A whole object was added or removed
+            change['old'] = redact_config({change['path']: change['old']})[change['path']]
+            change['new'] = redact_config({change['path']: change['new']})[change['path']]
+    return diff
+
+
+def describe_config_changes(previous: Dict[str, Any], config: Dict[str, Any]) -> List[str]:
+    """
+    Human-readable list of what changed between two configs, credentials redacted, e.g.
+    ["monitored_threads[0].check_interval: 300 → 60"].
+    """
+    return [describe_change(change) for change in redacted_diff(previous, config)]
+
+
+def unknown_keys(obj: Dict[str, Any], schema: Dict[str, Any]) -> List[Tuple[str, str]]:
+    """
+    Keys of obj that its JSON schema does not define, e.g. a misspelled "check_intervall".
+
+    Args:
+        obj: Config object
+        schema: Schema with properties and optionally patternProperties
+
+    Returns:
+        Unknown keys with a hint for likely typos, e.g. ("check_intervall", " (did you mean check_interval?)")
+    """
+    known = schema.get('properties', {})
+    patterns = schema.get('patternProperties', {})
+    unknown = []
+    for key in obj:
+        if key in known or any(re.search(pattern, key) for pattern in patterns):
+            continue
+        match = difflib.get_close_matches(key, known, n=1)
+        unknown.append((key, f' (did you mean {match[0]}?)' if match else ''))
+    return unknown
+
+
+def warn_unknown_keys(config: Dict[str, Any]):
+    """
+    Print a warning for every top-level key validate_config doesn't know.
+    Only thread entries reject unknown keys: top-level keys are kept as warnings so
+    a config.json shared with a newer version, which may add settings, still loads.
+    """
+    for key, hint in unknown_keys(config, CONFIG_SCHEMA):
+        print(f"Warning: Unknown config key {key}, ignoring it{hint}")
+
+
+def validate_config(config: Any) -> List[str]:
+    """
+    Validate a configuration dictionary.
+
+    Args:
+        config: Parsed configuration
+
+    Returns:
+        List of validation error messages (empty if valid)
+    """
+    if not isinstance(config, dict):
+        return ['Config must be a JSON object']
+
+    errors = []
+
+    if 'schema_version' in config and (not _is_int(config['schema_version'])
+                                       or config['schema_version'] < 1):
+        errors.append('schema_version: must be a positive integer')
+
+    for field, env_var in CREDENTIAL_ENV_VARS.items():
+        if os.environ.get(env_var) and field not in config:
+            continue
+        if not config.get(field) or not isinstance(config[field], str):
+            errors.append(f'{field}: required string (or set {env_var})')
+
+    if 'max_redirects' in config and (not _is_int(config['max_redirects']) or config['max_redirects'] < 0):
+        errors.append('max_redirects: must be a non-negative integer')
+
+    for field in ('max_threads', 'rate_limit_per_minute', 'max_pages_per_check'):
+        if field in config and (not _is_int(config[field]) or config[field] < 1):
+            errors.append(f'{field}: must be a positive integer')
+
+    if 'request_timeout' in config and (not isinstance(config['request_timeout'], (int, float))
+                                        or isinstance(config['request_timeout'], bool)
+                                        or config['request_timeout'] <= 0):
+        errors.append('request_timeout: must be a positive number of seconds')
+
+    for field in ('api_base_url', 'api_path'):
+        if field in config and (not isinstance(config[field], str) or not config[field]):
+            errors.append(f'{field}: must be a non-empty string')
+    if 'api_url' in config and ('api_base_url' in config or 'api_path' in config):
+        errors.append('api_url: give either api_url or api_base_url/api_path, not both')
+
+    api_urls = config.get('api_urls')
+    if api_urls is not None and (not isinstance(api_urls, list) or not api_urls
+                                 or not all(isinstance(url, str) and url for url in api_urls)):
+        errors.append('api_urls: must be a non-empty array of URLs')
+
+    user_agents = config.get('user_agents')
+    if user_agents is not None and (not isinstance(user_agents, list) or not user_agents
+                                    or not all(isinstance(ua, str) and ua for ua in user_agents)):
+        errors.append('user_agents: must be a non-empty array of strings')
+
+    if 'request_format' in config and config['request_format'] not in ('form', 'json'):
+        errors.append('request_format: must be "form" or "json"')
+
+    if 'server_port' in config and (not _is_int(config['server_port'])
+                                    or not 1 <= config['server_port'] <= 65535):
+        errors.append('server_port: must be an integer between 1 and 65535')
+
+    if 'bark_api_version' in config and config['bark_api_version'] not in (1, 2):
+        errors.append('bark_api_version: must be 1 or 2')
+
+    if config.get('bark_url_template') is not None and not is_valid_bark_url_template(config['bark_url_template']):
+        errors.append('bark_url_template: must contain {server_url} and only the placeholders '
+                      '{device_key}, {title} and {body}')
+
+    for field in ('bark_schedule', 'pushbullet_schedule', 'slack_schedule', 'console_schedule'):
+        if config.get(field) is not None:
+            errors.extend(validate_schedule(config[field], field))
+
+    if config.get('timezone') is not None:
+        errors.extend(validate_timezone(config['timezone'], 'timezone'))
+
+    if 'notification_locale' in config and config['notification_locale'] not in STRINGS:
+        errors.append(f"notification_locale: must be one of {', '.join(STRINGS)}")
+
+    if config.get('max_concurrent_notifications') is not None and (
+            not _is_int(config['max_concurrent_notifications']) or config['max_concurrent_notifications'] < 1):
+        errors.append('max_concurrent_notifications: must be a positive integer or null')
+
+    max_lengths = config.get('max_message_length')
+    if max_lengths is not None and (not isinstance(max_lengths, dict)
+                                    or not all(_is_int(v) and v > 0 for v in max_lengths.values())):
+        errors.append('max_message_length: must map sender names to positive integers')
+
+    proxy = config.get('proxy')
+    if proxy is not None:
+        if not isinstance(proxy, dict) or not proxy.get('url') or not isinstance(proxy['url'], str):
+            errors.append('proxy: must be an object with a url')
+        else:
+            for field in ('username', 'password'):
+                if proxy.get(field) is not None and not isinstance(proxy[field], str):
+                    errors.append(f'proxy.{field}: must be a string')
+
+    allowed_ips = config.get('server_allowed_ips')
+    if allowed_ips is not None:
+        if not isinstance(allowed_ips, list):
+            errors.append('server_allowed_ips: must be an array of addresses or CIDR ranges')
+        else:
+            for entry in allowed_ips:
+                try:
+                    if not isinstance(entry, str):
+                        raise ValueError(entry)
+                    ipaddress.ip_network(entry, strict=False)
+                except ValueError:
+                    errors.append(f'server_allowed_ips: invalid address or CIDR range {entry!r}')
+
+    threads = config.get('monitored_threads', [])
+    if not isinstance(threads, list):
+        errors.append('monitored_threads: must be an array')
+        return errors
+
+    seen_tids = set()
+    for i, thread in enumerate(threads):
+        prefix = f'monitored_threads[{i}]'
+        if not isinstance(thread, dict):
+            errors.append(f'{prefix}: must be an object')
+            continue
+
+        for key, hint in unknown_keys(thread, THREAD_SCHEMA):
+            errors.append(f'{prefix}.{key}: unknown field{hint}')
+
+        tid = thread.get('tid')
+        if 'url' in thread:
+            if 'tid' in thread:
+                errors.append(f'{prefix}: give either tid or url, not both')
+            tid = tid_from_url(thread['url'])
+            if tid is None:
+                errors.append(f'{prefix}.url: must be an NGA thread URL with a tid, '
+                              f'e.g. https://bbs.nga.cn/read.php?tid=12345678')
+        elif not _is_int(tid) or tid <= 0:
+            errors.append(f'{prefix}.tid: required positive integer (or give url)')
+            tid = None
+        if tid is not None and tid in seen_tids:
+            errors.append(f'{prefix}.tid: duplicate tid {tid}')
+        elif tid is not None:
+            seen_tids.add(tid)
+
+        if 'check_interval' in thread and (not _is_int(thread['check_interval'])
+                                           or thread['check_interval'] < 0):
+            errors.append(f'{prefix}.check_interval: must be a non-negative integer (0 for on demand)')
+
+        for field in ('author_filter', 'author_notification', 'author_exclusion'):
+            uids = thread.get(field)
+            if uids is not None and (not isinstance(uids, list)
+                                     or not all(_is_int(uid) for uid in uids)):
+                errors.append(f'{prefix}.{field}: must be an array of UIDs or null')
+
+        if 'enabled' in thread and not isinstance(thread['enabled'], bool):
+            errors.append(f'{prefix}.enabled: must be true or false')
+
+        for field in ('bark_group', 'note', 'created_at', 'updated_at'):
+            if thread.get(field) is not None and not isinstance(thread[field], str):
+                errors.append(f'{prefix}.{field}: must be a string')
+
+        for field in ('redact_spoilers', 'include_first_post', 'note_in_title'):
+            if field in thread and not isinstance(thread[field], bool):
+                errors.append(f'{prefix}.{field}: must be true or false')
+
+        disabled = thread.get('disable_notifiers')
+        if disabled is not None and (not isinstance(disabled, list)
+                                     or not all(name in SENDER_NAMES for name in disabled)):
+            errors.append(f'{prefix}.disable_notifiers: must be an array of notifier names '
+                          f'({", ".join(SENDER_NAMES)})')
+
+        if thread.get('filter') is not None:
+            errors.extend(validate_post_filter(thread['filter'], f'{prefix}.filter'))
+
+        if thread.get('notification_rules') is not None:
+            errors.extend(validate_notification_rules(thread['notification_rules'],
+                                                      f'{prefix}.notification_rules'))
+
+        if 'fetch_overlap_pages' in thread and (not _is_int(thread['fetch_overlap_pages'])
+                                                or thread['fetch_overlap_pages'] < 0):
+            errors.append(f'{prefix}.fetch_overlap_pages: must be a non-negative integer')
+
+        for field in ('max_content_chars', 'max_content_lines', 'max_content_sentences', 'max_pages_per_check'):
+            if thread.get(field) is not None and (not _is_int(thread[field]) or thread[field] < 1):
+                errors.append(f'{prefix}.{field}: must be a positive integer')
+
+    return errors
+
+
+def redact_config(config: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Return a copy of the configuration with credentials replaced by a placeholder.
+
+    Args:
+        config: Configuration dictionary
+
+    Returns:
+        Redacted copy
+    """
+    redacted = copy.deepcopy(config)
+    for field in CREDENTIAL_FIELDS:
+        if redacted.get(field):
+            redacted[field] = REDACTED
+    for parent, field in NESTED_CREDENTIAL_FIELDS:
+        if isinstance(redacted.get(parent), dict) and redacted[parent].get(field):
+            redacted[parent][field] = REDACTED
+    return redacted
+
+
+def restore_redacted(config: Dict[str, Any], current: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Replace redacted placeholders with the values from the current configuration.
+    Lets a redacted export be imported again without wiping credentials.
+
+    Args:
+        config: Incoming configuration
+        current: Configuration currently on disk
+
+    Returns:
+        Configuration with placeholders restored
+    """
+    restored = copy.deepcopy(config)
+    for field in CREDENTIAL_FIELDS:
+        if restored.get(field) == REDACTED:
+            if field in current:
+                restored[field] = current[field]
+            else:
+                del restored[field]
+    for parent, field in NESTED_CREDENTIAL_FIELDS:
+        if isinstance(restored.get(parent), dict) and restored[parent].get(field) == REDACTED:
+            if isinstance(current.get(parent), dict) and field in current[parent]:
+                restored[parent][field] = current[parent][field]
+            else:
+                del restored[parent][field]
+    return restored
+
+
+def find_monitored_thread(config: Dict[str, Any], tid: int) -> Optional[Dict[str, Any]]:
+    """
+    Find a thread entry in monitored_threads.
+
+    Args:
+        config: Configuration dictionary
+        tid: Thread ID
+
+    Returns:
+        The thread entry (not a copy), or None
+    """
+    for thread in config.get('monitored_threads', []):
+        if thread.get('tid') == tid:
+            return thread
+    return None
+
+
+def upsert_monitored_thread(config: Dict[str, Any], entry: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Add a thread entry to monitored_threads, or update the existing entry with the same tid.
+    Either way the stored entry's updated_at is set to the current UTC time; created_at
+    is set when the entry is added and kept on updates.
+
+    Args:
+        config: Configuration dictionary (modified in place)
+        entry: Thread entry with at least a tid
+
+    Returns:
+        The stored thread entry
+    """
+    existing = find_monitored_thread(config, entry['tid'])
+    if existing is not None:
+        existing.update({key: value for key, value in entry.items() if key != 'created_at'})
+        existing['updated_at'] = _utc_timestamp()
+        return existing
+
+    entry['created_at'] = entry['updated_at'] = _utc_timestamp()
+    config.setdefault('monitored_threads', []).append(entry)
+    return entry
+
+
+def set_thread_enabled(config: Dict[str, Any], tid: int, enabled: bool) -> Optional[Dict[str, Any]]:
+    """
+    Set the enabled flag of a monitored thread and its updated_at.
+
+    Args:
+        config: Configuration dictionary (modified in place)
+        tid: Thread ID
+        enabled: New value of the thread's enabled field
+
+    Returns:
+        The updated thread entry, or None if the thread is not configured
+    """
+    thread = find_monitored_thread(config, tid)
+    if thread is not None:
+        thread['enabled'] = enabled
+        thread['updated_at'] = _utc_timestamp()
+    return thread
+
+
+def remove_monitored_thread(config: Dict[str, Any], tid: int) -> bool:
+    """
+    Remove a thread entry from monitored_threads.
+
+    Args:
+        config: Configuration dictionary (modified in place)
+        tid: Thread ID
+
+    Returns:
+        True if an entry was removed
+    """
+    threads = config.get('monitored_threads', [])
+    remaining = [t for t in threads if t.get('tid') != tid]
+    config['monitored_threads'] = remaining
+    return len(remaining) != len(threads)
+
+
+def trim_inert_threads(config: Dict[str, Any]) -> List[int]:
+    """
+    Remove threads that can never trigger a notification: no author_notification
+    UIDs and no notification_rules (which covers author and keyword notifications).
+    A filter, including its keywords and author_names, only narrows notifications
+    down, so it doesn't keep a thread.
+
+    Args:
+        config: Configuration dictionary (modified in place)
+
+    Returns:
+        TIDs of the removed threads
+    """
+    threads = config.get('monitored_threads', [])
+    inert = [t for t in threads if not t.get('author_notification') and not t.get('notification_rules')]
+    config['monitored_threads'] = [t for t in threads if t not in inert]
+    return [t.get('tid') for t in inert]
+
+
+def _utc_timestamp() -> str:
+    """Current UTC time in ISO 8601, e.g. 2024-05-01T12:00:00+00:00."""
+    return datetime.now(timezone.utc).isoformat(timespec='seconds')
+
+
+def _is_int(value: Any) -> bool:
+    """Check for an integer that is not a bool."""
+    return isinstance(value, int) and not isinstance(value, bool)
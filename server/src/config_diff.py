@@ -0,0 +1,64 @@
+#!/usr/bin/env python3
+"""
+Recursive diff of JSON configuration values.
+"""
+
+import json
+from typing import Any, Dict, List
+
+_MISSING = object()
+
+
+def diff_config(old: Any, new: Any, path: str = '') -> List[Dict[str, Any]]:
+    """
+    Compare two parsed JSON values.
+
+    Objects are compared key by key and arrays index by index. Added or removed
+    entries are reported with None as the missing side.
+
+    Args:
+        old: Previous value
+        new: New value
+        path: Path of the values, e.g. "monitored_threads[0]"
+
+    Returns:
+        List of changes as {"path": ..., "old": ..., "new": ...}, e.g.
+        {"path": "monitored_threads[0].check_interval", "old": 300, "new": 60}
+    """
+    if isinstance(old, dict) and isinstance(new, dict):
+        changes = []
+        for key in list(old) + [k for k in new if k not in old]:
+            child = f'{path}.{key}' if path else str(key)
+            changes.extend(_diff_child(old.get(key, _MISSING), new.get(key, _MISSING), child))
+        return changes
+
+    if isinstance(old, list) and isinstance(new, list):
+        changes = []
+        for i in range(max(len(old), len(new))):
+            changes.extend(_diff_child(old[i] if i < len(old) else _MISSING,
+                                       new[i] if i < len(new) else _MISSING,
+                                       f'{path}[{i}]'))
+        return changes
+
+    if old == new and type(old) is type(new):
+        return []
+    return [{'path': path, 'old': old, 'new': new}]
+
+
+def describe_change(change: Dict[str, Any]) -> str:
+    """
+    Human-readable form of a diff_config change, e.g.
+    "monitored_threads[0].check_interval: 300 → 60". Missing values show as null.
+    """
+    old = json.dumps(change['old'], ensure_ascii=False)
+    new = json.dumps(change['new'], ensure_ascii=False)
+    return f"{change['path']}: {old} → {new}"
+
+
+def _diff_child(old: Any, new: Any, path: str) -> List[Dict[str, Any]]:
+    """Diff a child value that may be missing on either side."""
+    if old is _MISSING:
+        return [{'path': path, 'old': None, 'new': new}]
+    if new is _MISSING:
+        return [{'path': path, 'old': old, 'new': None}]
+    return diff_config(old, new, path)
@@ -0,0 +1,70 @@
+#!/usr/bin/env python3
+"""
+Config file migrations for NGA Reminder.
+
+Each migration upgrades a config dict by one schema_version. To make a
+breaking config change, bump CURRENT_SCHEMA_VERSION and register a function
+for the previous version:
+
+    def migrate_v1_to_v2(config):
+        config['new_field'] = config.pop('old_field', 'default')
+        return config
+
+    MIGRATIONS = {1: migrate_v1_to_v2}
+"""
+
+from typing import Any, Callable, Dict
+
+CURRENT_SCHEMA_VERSION = 1
+
+# Config files written before schema_version existed are version 1
+DEFAULT_SCHEMA_VERSION = 1
+
+# Migration from version N to N + 1, indexed by N
+MIGRATIONS: Dict[int, Callable[[Dict[str, Any]], Dict[str, Any]]] = {}
+
+
+def _is_valid_version(version: Any) -> bool:
+    """Whether schema_version is a positive integer; validate_config reports other values."""
+    return isinstance(version, int) and not isinstance(version, bool) and version >= 1
+
+
+def log_schema_version(config: Dict[str, Any], file=None):
+    """Print the schema_version of a config as read from disk, warning if it is outdated."""
+    version = config.get('schema_version', DEFAULT_SCHEMA_VERSION)
+    print(f"Config schema version: {version!r}", file=file)
+    if _is_valid_version(version) and version < CURRENT_SCHEMA_VERSION:
+        print(f"Warning: config is older than schema_version {CURRENT_SCHEMA_VERSION}; "
+              f"run `python main.py --migrate` to upgrade it", file=file)
+
+
+def migrate_config(config: Dict[str, Any]) -> bool:
+    """
+    Upgrade a configuration to CURRENT_SCHEMA_VERSION in place.
+
+    Args:
+        config: Parsed configuration
+
+    Returns:
+        True if the configuration was changed and should be written back.
+        Invalid schema_version values are left for validate_config to report.
+    """
+    version = config.get('schema_version', DEFAULT_SCHEMA_VERSION)
+    if not _is_valid_version(version):
+        return False
+    if version > CURRENT_SCHEMA_VERSION:
+        print(f"Warning: config schema_version {version} is newer than supported "
+              f"({CURRENT_SCHEMA_VERSION}); some settings may be ignored")
+        return False
+
+    changed = 'schema_version' not in config
+    while version < CURRENT_SCHEMA_VERSION:
+        migrated = MIGRATIONS[version](config)
+        config.clear()
+        config.update(migrated)
+        version += 1
+        changed = True
+        print(f"✓ Migrated config to schema_version {version}")
+
+    config['schema_version'] = version
+    return changed
@@ -0,0 +1,239 @@
+#!/usr/bin/env python3
+"""
+JSON Schema of config.json, for editor validation and autocompletion.
+
+Print it with `python main.py --print-schema > config.schema.json` and
+reference it from config.json via "$schema". Keep it in sync when adding
+config keys; validate_config remains the authoritative check.
+"""
+
+from typing import Any, Dict, Optional
+
+from .config_migrations import CURRENT_SCHEMA_VERSION
+from .notification import SENDER_NAMES
+
+
+def _string(description: str) -> Dict[str, Any]:
+    return {'type': 'string', 'description': description}
+
+
+def _integer(description: str, minimum: Optional[int] = None) -> Dict[str, Any]:
+    schema = {'type': 'integer', 'description': description}
+    if minimum is not None:
+        schema['minimum'] = minimum
+    return schema
+
+
+def _number(description: str) -> Dict[str, Any]:
+    return {'type': 'number', 'description': description}
+
+
+def _boolean(description: str) -> Dict[str, Any]:
+    return {'type': 'boolean', 'description': description}
+
+
+def _uids(description: str) -> Dict[str, Any]:
+    return {'type': ['array', 'null'], 'items': {'type': 'integer'}, 'description': description}
+
+
+SCHEDULE_SCHEMA = {
+    'type': 'array',
+    'description': 'Time windows in which the sender is active; always active if omitted',
+    'items': {
+        'type': 'object',
+        'properties': {
+            'days': {
+                'type': ['string', 'array'],
+                'items': {'type': 'string'},
+                'description': 'Day names (mon ... sun), ranges like "mon-fri", "weekdays", '
+                               '"weekends" or "daily" (default: every day)'
+            },
+            'start': {'type': 'string', 'pattern': r'^\d{1,2}:\d{2}$', 'description': 'HH:MM (default: 00:00)'},
+            'end': {'type': 'string', 'pattern': r'^\d{1,2}:\d{2}$',
+                    'description': 'HH:MM, before start to wrap past midnight (default: 00:00)'},
+            'timezone': _string('IANA timezone of start and end, e.g. America/New_York (default: timezone)')
+        },
+        'additionalProperties': False
+    }
+}
+
+POST_FILTER_SCHEMA = {
+    'type': 'object',
+    'description': 'Content filter applied before notifying; every criterion that is set must match',
+    'properties': {
+        'include_keywords': {'type': 'array', 'items': {'type': 'string'},
+                             'description': 'Notify only if the content contains one of these'},
+        'exclude_keywords': {'type': 'array', 'items': {'type': 'string'},
+                             'description': 'Never notify if the content contains one of these'},
+        'regex': _string('Regular expression the content must match'),
+        'author_uids': {'type': 'array', 'items': {'type': 'integer'}, 'description': 'Allowed author UIDs'},
+        'author_names': {'type': 'array', 'items': {'type': 'string'}, 'description': 'Allowed author names'},
+        'case_sensitive': _boolean('Match keywords, names and regex case-sensitively (default: false)'),
+        'redact_spoilers': _boolean('Replace [s]...[/s] spoiler text with [spoiler] (default: false)')
+    },
+    'additionalProperties': False
+}
+
+NOTIFICATION_RULE_SCHEMA = {
+    'type': 'object',
+    'description': 'Posts to notify about, in addition to author_notification',
+    'required': ['type'],
+    'properties': {
+        'type': {'type': 'string', 'enum': ['author', 'keywords', 'any'],
+                 'description': 'author: posts by uid, keywords: posts containing a keyword, any: every post'},
+        'uid': _integer('Author UID for "author" rules'),
+        'keywords': {'type': 'array', 'items': {'type': 'string'}, 'minItems': 1,
+                     'description': 'Keywords for "keywords" rules (case-insensitive)'}
+    },
+    'additionalProperties': False
+}
+
+THREAD_SCHEMA = {
+    'type': 'object',
+    'oneOf': [{'required': ['tid']}, {'required': ['url']}],
+    'properties': {
+        'tid': _integer('Thread ID to monitor', 1),
+        'url': _string('Thread URL instead of tid, e.g. https://nga.178.com/read.php?tid=12345678'),
+        'author_filter': _uids('Authors whose posts are saved (null for all)'),
+        'author_notification': _uids('Authors whose posts trigger notifications (null for none)'),
+        'author_exclusion': _uids('Authors whose posts never trigger notifications'),
+        'notification_rules': {'type': 'array', 'items': NOTIFICATION_RULE_SCHEMA,
+                               'description': 'A post is notified about if any rule matches'},
+        'check_interval': _integer('Seconds between checks, 0 to check only on demand (default: 300)', 0),
+        'enabled': _boolean('Whether to monitor this thread (default: true)'),
+        'note': _string('Label shown in thread listings and logs, e.g. "WoW strategy thread"'),
+        'note_in_title': _boolean('Prefix notification titles with the note (default: false)'),
+        'bark_group': _string("Bark group for this thread's notifications"),
+        'disable_notifiers': {'type': 'array', 'items': {'enum': list(SENDER_NAMES)},
+                              'description': 'Notifiers never used for this thread, e.g. ["bark"]'},
+        'redact_spoilers': _boolean('Replace [s]...[/s] spoiler text with [spoiler] (default: false)'),
+        'max_content_chars': _integer('Characters of post content shown in notifications (default: 100)', 1),
+        'max_content_lines': _integer('Show the first N non-empty lines instead of max_content_chars', 1),
+        'max_content_sentences': _integer('Show the first N sentences instead of max_content_chars', 1),
+        'include_first_post': _boolean("Add the thread's first post to its notifications (default: false)"),
+        'fetch_overlap_pages': _integer('Re-fetch this many pages before the first new page (default: 0)', 0),
+        'max_pages_per_check': _integer('Fetch at most the newest N pages of new posts per check '
+                                        '(default: top-level max_pages_per_check)', 1),
+        'filter': POST_FILTER_SCHEMA,
+        'created_at': _string('When the API added this thread (ISO 8601 UTC, set automatically)'),
+        'updated_at': _string('When the API last changed this thread (ISO 8601 UTC, set automatically)')
+    }
+}
+
+CONFIG_SCHEMA: Dict[str, Any] = {
+    '$schema': 'https://json-schema.org/draft/2020-12/schema',
+    'title': 'NGA Reminder configuration',
+    'type': 'object',
+    'required': ['ngaPassportUid', 'ngaPassportCid'],
+    'properties': {
+        '$schema': _string('Path or URL of this schema, for editors'),
+        'schema_version': {'type': 'integer', 'minimum': 1, 'maximum': CURRENT_SCHEMA_VERSION,
+                           'description': 'Config format version'},
+
+        # NGA access
+        'ngaPassportUid': _string('NGA login cookie (can be set with NGA_UID instead)'),
+        'ngaPassportCid': _string('NGA login cookie (can be set with NGA_CID instead)'),
+        'user_agent': _string('User-Agent header for NGA requests'),
+        'user_agents': {'type': 'array', 'items': {'type': 'string'}, 'minItems': 1,
+                        'description': 'User-Agent headers to rotate between per request, overriding user_agent'},
+        'api_url': _string('NGA app API endpoint (default: https://bbs.nga.cn/app_api.php)'),
+        'max_redirects': _integer('Redirects followed per NGA request, 0 for none (default: 30)', 0),
+        'api_base_url': _string('Scheme and host of the NGA API, instead of api_url (default: https://bbs.nga.cn)'),
+        'api_path': _string('Path of the NGA API on api_base_url (default: /app_api.php)'),
+        'api_urls': {'type': 'array', 'items': {'type': 'string'}, 'minItems': 1,
+                     'description': 'NGA API endpoints to fail over between; overrides api_url'},
+        'request_format': {'type': 'string', 'enum': ['form', 'json'],
+                           'description': 'Body encoding of NGA API requests (default: form)'},
+        'proxy': {
+            'type': 'object',
+            'description': 'HTTP(S) proxy for NGA requests',
+            'required': ['url'],
+            'properties': {
+                'url': _string('Proxy URL, e.g. http://proxy.example.com:3128'),
+                'username': _string('Username for proxy Basic authentication'),
+                'password': _string('Password for proxy Basic authentication')
+            },
+            'additionalProperties': False
+        },
+        'request_timeout': _number('Seconds to wait for an NGA API response (default: 30)'),
+        'max_threads': _integer('Concurrent page fetches during the initial crawl (default: 5)', 1),
+        'rate_limit_per_minute': _integer('Maximum NGA requests per minute (default: 30)', 1),
+
+        # Monitoring
+        'monitored_threads': {'type': 'array', 'items': THREAD_SCHEMA, 'description': 'Threads to monitor'},
+        'error_backoff_factor': _number('Interval multiplier per consecutive failed check (default: 2.0)'),
+        'max_error_backoff_secs': _integer('Upper bound of the backed-off interval (default: 3600)', 1),
+        'rate_limit_max_wait_secs': _integer('Longest wait after a 429 response (default: 60)', 0),
+        'error_alert_threshold': _integer('Consecutive failures before an alert, 0 to disable (default: 5)', 0),
+        'error_alert_repeat_hours': _number('Minimum hours between repeated failure alerts (default: 6)'),
+        'batch_notifications': _boolean('One summary notification per thread and cycle (default: false)'),
+        'max_cycle_secs': _integer('Postpone threads still due after a check cycle ran this long, 0 for no limit '
+                                   '(default: 600)', 0),
+        'max_pages_per_check': _integer('Default per-thread limit of pages of new posts fetched per check '
+                                        '(default: no limit)', 1),
+        'check_history_size': _integer('Recent check results kept per thread (default: 10)', 0),
+
+        # Notifications
+        'timezone': _string('IANA timezone of sender schedules, e.g. Asia/Shanghai (default: system local time)'),
+        'notification_locale': {'type': 'string', 'enum': ['zh-CN', 'en-US'],
+                                'description': 'Language of notification text (default: zh-CN)'},
+        'code_block_max_lines': _integer('Code lines kept per [code] block for Markdown senders (default: 5)', 0),
+        'max_message_length': {'type': 'object', 'additionalProperties': {'type': 'integer', 'minimum': 1},
+                               'description': 'Maximum message length per sender name, e.g. {"bark": 1000}'},
+        'max_concurrent_notifications': {'type': ['integer', 'null'], 'minimum': 1,
+                                         'description': 'Notifications sent at the same time at most (default: unlimited)'},
+        'bark_enabled': _boolean('Send notifications with Bark'),
+        'bark_server_url': _string('Bark server URL, e.g. https://api.day.app'),
+        'bark_device_key': _string('Bark device key'),
+        'bark_sound': _string('Bark sound (default: default)'),
+        'bark_group': _string('Bark group (default: NGA)'),
+        'bark_icon': _string('Bark icon URL'),
+        'bark_save': _boolean('Save notifications in the Bark history (default: false)'),
+        'bark_is_archive': _boolean('Bark isArchive parameter (default: false)'),
+        'bark_auto_copy': _boolean('Bark autoCopy parameter (default: false)'),
+        'bark_timeout': _number('Bark request timeout in seconds (default: 10)'),
+        'bark_api_version': {'type': 'integer', 'enum': [1, 2],
+                             'description': 'Bark API format: 1 (GET /<key>) or 2 (POST /push with JSON) (default: 1)'},
+        'bark_url_template': _string('Push URL for Bark-compatible servers, e.g. {server_url}/push; '
+                                     'placeholders {server_url}, {device_key}, {title}, {body}'),
+        'pushbullet_enabled': _boolean('Send notifications with Pushbullet'),
+        'pushbullet_api_token': _string('Pushbullet access token'),
+        'pushbullet_device_iden': _string('Pushbullet device to push to'),
+        'pushbullet_channel_tag': _string('Pushbullet channel to push to'),
+        'pushbullet_timeout': _number('Pushbullet request timeout in seconds (default: 10)'),
+        'slack_enabled': _boolean('Send notifications to Slack'),
+        'slack_webhook_url': _string('Slack incoming webhook URL (https://hooks.slack.com/...)'),
+        'slack_username': _string('Slack username override'),
+        'slack_timeout': _number('Slack request timeout in seconds (default: 10)'),
+        'console_notification_enabled': _boolean('Print notifications to the console (default: true)'),
+        'console_log_file': _string('Append console notifications to this file instead of stdout'),
+
+        # Config file handling
+        'config_backup_on_write': _boolean('Copy config.json to config.json.bak before rewriting it (default: true)'),
+        'config_audit_log': _string('File to append a JSON line to for every config write'),
+
+        # Server
+        'api_key': _string('Shared secret for protected API routes (X-API-Key header)'),
+        'server_host': _string('Host to bind to (default: 127.0.0.1)'),
+        'server_port': {'type': 'integer', 'minimum': 1, 'maximum': 65535, 'description': 'Port (default: 8000)'},
+        'server_socket_path': _string('Serve on this Unix domain socket instead of TCP'),
+        'server_socket_mode': {'type': ['string', 'integer'], 'description': 'Octal socket permissions (default: "660")'},
+        'server_allowed_ips': {'type': 'array', 'items': {'type': 'string'},
+                               'description': 'IP addresses or CIDR ranges allowed to use the API'},
+        'server_compression': _boolean('Gzip responses of 1000 bytes or more (default: true)'),
+        'server_max_request_body_bytes': _integer('Largest accepted request body (default: 1048576)', 1),
+        'server_tls_certfile': _string('TLS certificate file; enables HTTPS'),
+        'server_tls_keyfile': _string('TLS private key file'),
+        'server_mtls_ca_certs': _string('CA bundle for verifying client certificates'),
+        'server_mtls_require_client_cert': _boolean('Reject clients without a valid certificate (default: true)'),
+        'pid_file': _string('Write the server process ID to this file'),
+
+        # Error reporting
+        'sentry_dsn': _string('Report check failures and uncaught exceptions to Sentry'),
+        'sentry_environment': _string('Sentry environment tag (default: production)')
+    },
+    'patternProperties': {
+        '^(bark|pushbullet|slack|console)_schedule$': SCHEDULE_SCHEMA,
+        '^(bark|pushbullet|slack|console)_supports_markdown$': _boolean('Send the Markdown message variant to this sender')
+    }
+}
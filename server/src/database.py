@@ -267,6 +267,24 @@ class NGADatabase:
         
         return [dict(row) for row in self.cursor.fetchall()]
     
+    def get_post_by_number(self, tid: int, post_number: int) -> Optional[Dict[str, Any]]:
+        """
+        Get a single post by its post number within a thread.
+        
+        Args:
+            tid: Thread ID
+            post_number: Post number (floor) in the thread
+            
+        Returns:
+            Post dictionary or None
+        """
+        self.cursor.execute(
+            'SELECT * FROM posts WHERE tid = ? AND post_number = ?',
+            (tid, post_number)
+        )
+        row = self.cursor.fetchone()
+        return dict(row) if row else None
+    
     def get_thread_stats(self) -> List[Dict[str, Any]]:
         """
         Get statistics for all threads.
@@ -354,6 +372,41 @@ def parse_page_result(page_data: Dict[str, Any]) -> tuple[Dict[str, Any], List[D
     return thread, posts
 
 
+def merge_pages(pages: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """
+    Merge raw API pages into one list of posts.
+    
+    Pages are ordered by currentPage and posts that appear on more than one
+    page (e.g. when posts shift between fetches) are kept once.
+    
+    Args:
+        pages: Raw API responses, in any order
+        
+    Returns:
+        Post dictionaries as from parse_page_result, in page order
+    """
+    posts = []
+    seen = set()
+    for page_data in sorted(pages, key=lambda p: p.get('currentPage', 0)):
+        _, page_posts = parse_page_result(page_data)
+        for post in page_posts:
+            if post['post_number'] in seen:
+                continue
+            seen.add(post['post_number'])
+            posts.append(post)
+    return posts
+
+
+def find_post_by_pid(posts: List[Dict[str, Any]], pid: int) -> Optional[Dict[str, Any]]:
+    """
+    Find a post by pid in a list of posts, e.g. from merge_pages.
+    
+    Returns:
+        The first post with that pid, or None
+    """
+    return next((post for post in posts if post['pid'] == pid), None)
+
+
 if __name__ == '__main__':
     # Example usage
     db = NGADatabase('test.db')
@@ -0,0 +1,67 @@
+#!/usr/bin/env python3
+"""
+Localized strings for notifications.
+Selected with the notification_locale config key.
+"""
+
+from typing import Dict
+
+DEFAULT_LOCALE = 'zh-CN'
+
+STRINGS: Dict[str, Dict[str, str]] = {
+    'zh-CN': {
+        'view_post': '查看帖子',
+        'posted': '发布于 {time}',
+        'new_posts': '{count} 条新帖子',
+        'and_more': '…还有 {count} 条',
+        'thread_summary': '主楼',
+        'check_failing_title': 'NGAReminder: 帖子检查失败',
+        'check_failing_message': 'TID {tid} 已连续 {count} 次检查失败。最近错误: {error}',
+        'credentials_expired_title': 'NGAReminder: 登录已失效',
+        'credentials_expired_message': 'NGA 返回未登录 (-4)，监控已暂停。请更新 ngaPassportUid / ngaPassportCid 后重新加载配置。',
+        'just_now': '刚刚',
+        'minute_ago': '{n} 分钟前',
+        'minutes_ago': '{n} 分钟前',
+        'hour_ago': '{n} 小时前',
+        'hours_ago': '{n} 小时前',
+        'day_ago': '{n} 天前',
+        'days_ago': '{n} 天前',
+    },
+    'en-US': {
+        'view_post': 'View post',
+        'posted': 'posted {time}',
+        'new_posts': '{count} new posts',
+        'and_more': '... and {count} more',
+        'thread_summary': 'Thread summary',
+        'check_failing_title': 'NGAReminder: thread check failing',
+        'check_failing_message': 'TID {tid} failed {count} consecutive checks. Last error: {error}',
+        'credentials_expired_title': 'NGAReminder: credentials expired',
+        'credentials_expired_message': 'NGA reports not logged in (-4), monitoring is paused. '
+                                       'Update ngaPassportUid / ngaPassportCid and reload the config.',
+        'just_now': 'just now',
+        'minute_ago': '{n} minute ago',
+        'minutes_ago': '{n} minutes ago',
+        'hour_ago': '{n} hour ago',
+        'hours_ago': '{n} hours ago',
+        'day_ago': '{n} day ago',
+        'days_ago': '{n} days ago',
+    },
+}
+
+
+def translate(key: str, locale: str = DEFAULT_LOCALE, **kwargs) -> str:
+    """
+    Look up a localized string and fill in its placeholders.
+    Unknown locales fall back to en-US.
+
+    Args:
+        key: String key, e.g. "view_post"
+        locale: Locale name, e.g. "zh-CN" or "en-US"
+        **kwargs: Placeholder values
+
+    Returns:
+        Localized string
+    """
+    strings = STRINGS.get(locale, STRINGS['en-US'])
+    template = strings.get(key, STRINGS['en-US'][key])
+    return template.format(**kwargs)
@@ -4,36 +4,218 @@ Thread monitor for NGA BBS.
 Periodically checks monitored threads for new posts.
 """
 
+import copy
 import json
+import os
+import signal
+import sys
+import threading
 import time
+import uuid
 import argparse
-from datetime import datetime
-from typing import List, Dict, Any, Optional, Set
-from .database import NGADatabase, parse_page_result
-from .nga_crawler import NGACrawler
-from .notification import NotificationManager
+from collections import deque
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta
+from typing import Callable, List, Dict, Any, Optional, Set
+from .database import NGADatabase, merge_pages, parse_page_result
+from .nga_crawler import NGACrawler, NgaApiError, thread_metadata
+from .notification import NotificationManager, NotificationSender
+from .observability import init_sentry, capture_exception, capture_message
+from .bbcode import strip_bbcode, redact_spoilers, format_code_blocks
+from .config import (
+    DEFAULT_CONFIG_PATH, create_config_from_env, describe_config_changes, find_monitored_thread,
+    load_config, validate_config, warn_unknown_keys
+)
+from .config_migrations import log_schema_version
+from .i18n import DEFAULT_LOCALE, translate
+from .post_filter import PostFilter, thread_matches_post
+from .util import (
+    close_code_fences, format_relative_time, post_url, thread_url, truncate_lines, truncate_sentences,
+    truncate_text
+)
+
+
+@dataclass
+class CheckHistoryEntry:
+    """Outcome of a single check of a monitored thread."""
+    checked_at: datetime
+    posts_found: int = 0
+    new_notifications_sent: int = 0
+    error: Optional[str] = None
+    duration_ms: int = 0
+    check_id: Optional[str] = None
+
+
+@dataclass
+class ThreadCheckState:
+    """In-memory runtime state of a monitored thread, updated after every check."""
+    last_checked_at: Optional[datetime] = None
+    consecutive_errors: int = 0
+    last_error: Optional[str] = None
+    no_new_posts_streak: int = 0
+    total_notifications: int = 0
+    effective_interval: Optional[int] = None  # Backed-off interval while failing
+    last_error_alert_at: Optional[datetime] = None
+    snooze_until: Optional[datetime] = None  # Scheduled checks are skipped until then
+    retry_at: Optional[datetime] = None  # Rate limited by NGA: not checked again before then
+    history: deque = field(default_factory=deque)  # Recent CheckHistoryEntry items, oldest first
+
+
+def next_check_time(last_checked: Optional[str], check_interval: int,
+                    state: Optional[ThreadCheckState]) -> Optional[datetime]:
+    """
+    When run_loop will next check a thread.
+    
+    Args:
+        last_checked: last_checked column of the thread's monitored_threads row
+        check_interval: Configured check interval in seconds, 0 for on-demand only
+        state: Runtime state of the thread, if any
+        
+    Returns:
+        Time of the next check, or None if the thread has never been checked (due now)
+        or is only checked on demand
+    """
+    if check_interval == 0:
+        return None
+    next_check = None
+    # Failed checks don't update last_checked, so back off from the failed attempt
+    if state and state.consecutive_errors > 0 and state.last_checked_at:
+        next_check = state.last_checked_at + timedelta(seconds=state.effective_interval or check_interval)
+    elif last_checked:
+        try:
+            # SQLite datetime format: 'YYYY-MM-DD HH:MM:SS'
+            next_check = datetime.strptime(last_checked, '%Y-%m-%d %H:%M:%S') + timedelta(seconds=check_interval)
+        except ValueError:
+            pass
+    # A snoozed or rate-limited thread resumes its schedule when the snooze or wait ends
+    for until in (state.snooze_until, state.retry_at) if state else ():
+        if until and (next_check is None or next_check < until):
+            next_check = until
+    return next_check
+
+
+def describe_thread(tid: int, note: Optional[str] = None, title: Optional[str] = None) -> str:
+    """
+    Label for a thread in log output: "Thread #12345 (note)", falling back to the
+    title when the thread has no note, or just "Thread #12345" without either.
+    """
+    label = note or title
+    return f"Thread #{tid} ({label})" if label else f"Thread #{tid}"
 
 
 class ThreadMonitor:
     """Monitor NGA threads for new posts."""
     
-    def __init__(self, db_path: str = "data/nga_data.db", config_path: str = "config/config.json"):
+    def __init__(self, db_path: str = "data/nga_data.db", config_path: str = DEFAULT_CONFIG_PATH,
+                 senders: Optional[List[NotificationSender]] = None):
         """
         Initialize thread monitor.
         
         Args:
             db_path: Path to SQLite database
             config_path: Path to crawler config file
+            senders: Notification senders to use instead of the ones configured,
+                e.g. a MockSender in tests. Kept across config reloads.
         """
         self.db = NGADatabase(db_path)
         self.crawler = NGACrawler(config_path)
         self.config_path = config_path
+        self.senders = senders
+        
+        # Set when NGA reports the cookies are no longer logged in
+        self.credentials_expired = False
+        
+        self.thread_states: Dict[int, ThreadCheckState] = {}
+        
+        # First post of each include_first_post thread, from its latest check
+        self.first_posts: Dict[int, Dict[str, Any]] = {}
         self._init_monitor_tables()
         
+        config = load_config(config_path)
+        warn_unknown_keys(config)
+        self._apply_config(config)
+        init_sentry(config)
+    
+    def _apply_config(self, config: Dict[str, Any]):
+        """Apply monitor and notification settings from a loaded config."""
+        self.config = config
+        
         # Initialize notification system
-        with open(config_path, 'r', encoding='utf-8') as f:
-            config = json.load(f)
         self.notification_manager = NotificationManager(config)
+        if self.senders is not None:
+            self.notification_manager.senders = list(self.senders)
+        
+        # Exponential backoff for threads whose checks keep failing
+        self.error_backoff_factor = config.get('error_backoff_factor', 2.0)
+        self.max_error_backoff_secs = config.get('max_error_backoff_secs', 3600)
+        
+        # Longest wait after an NGA 429 response before the thread is retried
+        self.rate_limit_max_wait_secs = config.get('rate_limit_max_wait_secs', 60)
+        
+        # Notify when a thread keeps failing (0 disables the alert)
+        self.error_alert_threshold = config.get('error_alert_threshold', 5)
+        self.error_alert_repeat_hours = config.get('error_alert_repeat_hours', 6)
+        
+        # Number of recent check results kept per thread
+        self.check_history_size = config.get('check_history_size', 10)
+        
+        # Language of notification text
+        self.locale = config.get('notification_locale', DEFAULT_LOCALE)
+        
+        # Code lines kept per [code] block for senders that support Markdown
+        self.code_block_max_lines = config.get('code_block_max_lines', 5)
+        
+        # One summary notification per thread and cycle instead of one per post
+        self.batch_notifications = config.get('batch_notifications', False)
+        
+        # Threads still due when a cycle has run this long wait for the next cycle (0 for no limit)
+        self.max_cycle_secs = config.get('max_cycle_secs', 600)
+        
+        # Default for threads without their own max_pages_per_check (None for no limit)
+        self.max_pages_per_check = config.get('max_pages_per_check')
+    
+    def on_config_saved(self, config: Dict[str, Any], config_path: str):
+        """
+        save_config listener: apply settings written to this monitor's config file
+        right away, e.g. a thread's filter changed through the API. Thread rows
+        are not synced; the API updates those itself.
+        """
+        if os.path.abspath(config_path) == os.path.abspath(self.config_path):
+            self._apply_config(config)
+    
+    def reload_config(self, stop_event=None) -> Dict[str, Any]:
+        """
+        Reload config.json and apply it without restarting.
+        Rebuilds the crawler and notification senders and syncs monitored threads.
+        An invalid config is rejected and the current settings are kept.
+        
+        Args:
+            stop_event: Optional threading.Event to signal early stop of the thread sync
+            
+        Returns:
+            Summary of the thread sync, or an error
+        """
+        try:
+            config = load_config(self.config_path)
+        except (OSError, json.JSONDecodeError) as e:
+            print(f"✗ Config reload failed: {e}")
+            return {'error': f'Could not read config: {e}'}
+        
+        errors = validate_config(config)
+        if errors:
+            print(f"✗ Config reload failed, keeping current settings: {'; '.join(errors)}")
+            return {'error': 'Invalid config', 'errors': errors}
+        
+        warn_unknown_keys(config)
+        changes = describe_config_changes(self.config, config)
+        self.crawler = NGACrawler(self.config_path)
+        self._apply_config(config)
+        # New credentials may have been configured
+        self.credentials_expired = False
+        print(f"✓ Config reloaded ({len(changes)} change(s))")
+        for change in changes:
+            print(f"  {change}")
+        return self.load_from_config(stop_event=stop_event)
     
     def _init_monitor_tables(self):
         """Initialize monitoring tables if they don't exist."""
@@ -60,8 +242,30 @@ class ThreadMonitor:
                 FOREIGN KEY (tid) REFERENCES threads(tid) ON DELETE CASCADE
             );
             
+            CREATE TABLE IF NOT EXISTS notification_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                tid INTEGER NOT NULL,
+                pid INTEGER,
+                author_uid INTEGER,
+                author_name TEXT,
+                title TEXT,
+                message TEXT,
+                url TEXT,
+                sent_at TIMESTAMP DEFAULT (datetime('now', 'localtime'))
+            );
+            
+            -- Notifications up to last_history_id don't count towards a thread's total
+            CREATE TABLE IF NOT EXISTS notification_stats_resets (
+                tid INTEGER PRIMARY KEY,
+                last_history_id INTEGER NOT NULL,
+                reset_at TIMESTAMP DEFAULT (datetime('now', 'localtime'))
+            );
+            
             CREATE INDEX IF NOT EXISTS idx_monitored_active ON monitored_threads(is_active);
             CREATE INDEX IF NOT EXISTS idx_monitoring_events_tid ON monitoring_events(tid);
+            CREATE INDEX IF NOT EXISTS idx_notification_history_tid ON notification_history(tid, sent_at);
+            CREATE INDEX IF NOT EXISTS idx_notification_history_sent ON notification_history(sent_at);
+            CREATE INDEX IF NOT EXISTS idx_notification_history_author ON notification_history(author_uid, sent_at);
         ''')
         self.db.conn.commit()
     
@@ -193,8 +397,36 @@ class ThreadMonitor:
             print(f"Error removing thread: {e}")
             return False
     
+    def reset_last_seen(self, tid: int, post_number: int = 0) -> Optional[Dict[str, Any]]:
+        """
+        Forget the posts of a thread after post_number so the next check fetches them
+        again and treats them as new, re-sending their notifications.
+        The removed posts are re-saved by that check.
+        
+        Args:
+            tid: Thread ID
+            post_number: Last post number to keep (0 keeps only the first post)
+            
+        Returns:
+            {'tid', 'post_number', 'posts_removed', 'total_posts'}, or None if the thread is not stored
+        """
+        thread = self.db.get_thread(tid)
+        if not thread:
+            return None
+        
+        self.db.cursor.execute('DELETE FROM posts WHERE tid = ? AND post_number > ?', (tid, post_number))
+        posts_removed = self.db.cursor.rowcount
+        # The next check fetches from the page holding post post_number + 1
+        total_posts = min(thread['total_posts'], post_number + 1)
+        self.db.cursor.execute('UPDATE threads SET total_posts = ? WHERE tid = ?', (total_posts, tid))
+        self._log_event(tid, 'reset', posts_removed, f'Reset last seen post to #{post_number}')
+        self.db.conn.commit()
+        
+        return {'tid': tid, 'post_number': post_number, 'posts_removed': posts_removed,
+                'total_posts': total_posts}
+    
     def list_monitored(self) -> List[Dict[str, Any]]:
-        """Get list of monitored threads."""
+        """Get list of monitored threads. Threads disabled in the config are left out."""
         self.db.cursor.execute('''
             SELECT 
                 m.*,
@@ -206,15 +438,49 @@ class ThreadMonitor:
             WHERE m.is_active = 1
             ORDER BY m.last_checked DESC
         ''')
-        return [dict(row) for row in self.db.cursor.fetchall()]
+        return [dict(row) for row in self.db.cursor.fetchall()
+                if (find_monitored_thread(self.config, row['tid']) or {}).get('enabled', True)]
     
-    def load_from_config(self, config_path: Optional[str] = None, stop_event=None) -> Dict[str, Any]:
+    def list_config_threads(self, fetch_titles: bool = True) -> List[Dict[str, Any]]:
+        """
+        List the threads configured in monitored_threads with their stored progress.
+        
+        Args:
+            fetch_titles: Fetch the first page of threads not in the database yet to get their title
+            
+        Returns:
+            List of dictionaries with tid, enabled, last_seen_post_number, check_interval and title
+        """
+        threads = []
+        for entry in self.config.get('monitored_threads', []):
+            tid = entry.get('tid')
+            last_seen = self._max_post_number(tid)
+            
+            thread = self.db.get_thread(tid)
+            title = thread['title'] if thread else ''
+            if not title and fetch_titles:
+                metadata = self.crawler.fetch_thread_metadata(tid)
+                if metadata:
+                    title = metadata['title']
+            
+            threads.append({
+                'tid': tid,
+                'enabled': entry.get('enabled', True),
+                'last_seen_post_number': last_seen,
+                'check_interval': entry.get('check_interval', 300),
+                'title': title
+            })
+        return threads
+    
+    def load_from_config(self, config_path: Optional[str] = None, stop_event=None,
+                         tids: Optional[Set[int]] = None) -> Dict[str, Any]:
         """
         Load and sync monitored threads from config file.
         
         Args:
             config_path: Path to config file (defaults to self.config_path)
             stop_event: Optional threading.Event to signal early stop
+            tids: Only sync these threads (default: all configured threads)
             
         Returns:
             Summary of sync operation
@@ -222,14 +488,15 @@ class ThreadMonitor:
         config_path = config_path or self.config_path
         
         try:
-            with open(config_path, 'r', encoding='utf-8') as f:
-                config = json.load(f)
+            config = load_config(config_path)
         except FileNotFoundError:
             return {'error': f'Config file not found: {config_path}'}
         except json.JSONDecodeError as e:
             return {'error': f'Invalid JSON in config file: {e}'}
         
         monitored_threads = config.get('monitored_threads', [])
+        if tids is not None:
+            monitored_threads = [t for t in monitored_threads if t.get('tid') in tids]
         
         if not monitored_threads:
             return {'error': 'No monitored_threads defined in config file'}
@@ -237,6 +504,7 @@ class ThreadMonitor:
         added = 0
         updated = 0
         skipped = 0
+        disabled = []
         errors = []
         
         print(f"\nSyncing {len(monitored_threads)} thread(s) from config...\n")
@@ -255,7 +523,14 @@ class ThreadMonitor:
             enabled = thread_config.get('enabled', True)
             
             if not enabled:
+                # Stop checking it if it was monitored before being disabled
+                self.db.cursor.execute(
+                    'UPDATE monitored_threads SET is_active = 0 WHERE tid = ?',
+                    (tid,)
+                )
+                self.db.conn.commit()
                 print(f"  ⊘ TID {tid}: Skipped (disabled in config)")
+                disabled.append(tid)
                 skipped += 1
                 continue
             
@@ -409,6 +684,8 @@ class ThreadMonitor:
         print(f"  Added: {added}")
         print(f"  Updated: {updated}")
         print(f"  Skipped: {skipped}")
+        if disabled:
+            print(f"  ⚠ Disabled in config, not checked: {', '.join(map(str, disabled))}")
         if errors:
             print(f"  Errors: {len(errors)}")
             for err in errors:
@@ -422,7 +699,9 @@ class ThreadMonitor:
             'errors': errors
         }
     
-    def check_thread(self, tid: int, verbose: bool = True) -> Dict[str, Any]:
+    def check_thread(self, tid: int, verbose: bool = True,
+                     monitor_config: Optional[Dict[str, Any]] = None,
+                     notification_queue: Optional[List[tuple]] = None) -> Dict[str, Any]:
         """
         Check a single thread for new posts.
         Compares vrows (total posts) and fetches only new pages if needed.
@@ -430,21 +709,37 @@ class ThreadMonitor:
         Args:
             tid: Thread ID
             verbose: Print detailed output
+            monitor_config: Monitored thread row (as returned by list_monitored).
+                Looked up from the database when omitted.
+            notification_queue: If given, (tid, thread_title, post, check_id) entries for posts
+                to notify about are appended here for flush_notifications instead of being sent
             
         Returns:
-            Dictionary with check results
+            Dictionary with check results, including duration_ms and check_id, a random ID
+            also shown in this check's log output and notifications to correlate them
         """
-        # Get monitoring config and thread data
-        self.db.cursor.execute(
-            'SELECT * FROM monitored_threads WHERE tid = ? AND is_active = 1',
-            (tid,)
-        )
-        monitor_config = self.db.cursor.fetchone()
-        
-        if not monitor_config:
-            return {'error': f'Thread {tid} not monitored'}
-        
-        monitor_config = dict(monitor_config)
+        started = time.monotonic()
+        check_id = str(uuid.uuid4())
+        result = self._check_thread(tid, verbose, monitor_config, notification_queue, check_id)
+        result['duration_ms'] = int((time.monotonic() - started) * 1000)
+        result['check_id'] = check_id
+        return result
+    
+    def _check_thread(self, tid: int, verbose: bool, monitor_config: Optional[Dict[str, Any]],
+                      notification_queue: Optional[List[tuple]], check_id: str) -> Dict[str, Any]:
+        """Check a single thread for new posts. See check_thread."""
+        # Get monitoring config unless the caller already has it
+        if monitor_config is None:
+            self.db.cursor.execute(
+                'SELECT * FROM monitored_threads WHERE tid = ? AND is_active = 1',
+                (tid,)
+            )
+            monitor_config = self.db.cursor.fetchone()
+            
+            if not monitor_config:
+                return {'error': f'Thread {tid} not monitored'}
+            
+            monitor_config = dict(monitor_config)
         author_filter = monitor_config['author_filter']
         
         # Parse author filter
@@ -461,23 +756,49 @@ class ThreadMonitor:
         
         if verbose:
             print(f"\n{'='*80}")
-            print(f"Checking thread {tid}: {thread['title']}")
+            print(f"Checking {self.describe_thread(tid, thread['title'])}")
             print(f"{'='*80}")
+            print(f"Check ID: {check_id}")
             print(f"Last check: {monitor_config.get('last_checked', 'Never')}")
             print(f"Stored total posts: {old_total_posts}")
         
         try:
-            # Fetch page 1 to get current total post count
+            # Fetch page 1 to get current total post count. Not fetch_thread_metadata:
+            # its posts are reused below for include_first_post and small threads.
             first_page = self.crawler.fetch_page(tid, 1)
             
             if not first_page:
-                self._log_event(tid, 'error', 0, 'Failed to fetch thread')
-                return {'error': 'Failed to fetch thread'}
+                error = getattr(self.crawler, 'last_error', None)
+                error_msg = f'Failed to fetch thread: {error}' if error else 'Failed to fetch thread'
+                self._log_event(tid, 'error', 0, error_msg)
+                capture_message(f'Failed to fetch thread {tid}')
+                result = {'error': error_msg}
+                if error:
+                    result['error_kind'] = error.kind
+                if error and error.kind == 'nga_api' and error.code == NgaApiError.NOT_LOGGED_IN:
+                    self._pause_for_credentials()
+                if error and error.kind == 'rate_limited':
+                    # Retry once the wait NGA asks for (capped) is over, without backing off.
+                    # Not slept here, so the cycle and API requests aren't held up.
+                    wait = min(error.retry_after_secs or self.rate_limit_max_wait_secs,
+                               self.rate_limit_max_wait_secs)
+                    if verbose:
+                        print(f"  ⏳ Rate limited by NGA, retrying in {wait}s")
+                    result['retry'] = True
+                    result['retry_after_secs'] = wait
+                return result
+            
+            # The first post is on page 1, which every check fetches
+            if (find_monitored_thread(self.config, tid) or {}).get('include_first_post'):
+                _, first_page_posts = parse_page_result(first_page)
+                if first_page_posts and first_page_posts[0]['post_number'] == 0:
+                    self.first_posts[tid] = first_page_posts[0]
             
             # Get current thread stats
-            current_total_posts = first_page.get('vrows', 0)
-            current_total_pages = first_page.get('totalPage', 0)
-            posts_per_page = first_page.get('perPage', 20)
+            metadata = thread_metadata(first_page)
+            current_total_posts = metadata['total_posts']
+            current_total_pages = metadata['total_pages']
+            posts_per_page = metadata['per_page']
             
             if verbose:
                 print(f"Current total posts: {current_total_posts}")
@@ -506,6 +827,7 @@ class ThreadMonitor:
                     'tid': tid,
                     'new_posts': 0,
                     'total_posts': current_total_posts,
+                    'notifications_sent': 0,
                     'posts': []
                 }
             
@@ -526,23 +848,37 @@ class ThreadMonitor:
             
             end_page = current_total_pages
             
+            # Optionally re-fetch earlier pages; already stored posts are skipped below
+            thread_options = find_monitored_thread(self.config, tid) or {}
+            start_page = max(1, start_page - thread_options.get('fetch_overlap_pages', 0))
+            
+            # A long-unchecked thread could mean hundreds of pages: skip all but the newest
+            max_pages = thread_options.get('max_pages_per_check', self.max_pages_per_check)
+            if max_pages and end_page - start_page + 1 > max_pages:
+                skipped = end_page - max_pages - start_page + 1
+                start_page = end_page - max_pages + 1
+                print(f"  ⚠ TID {tid}: {skipped} page(s) of new posts exceed max_pages_per_check "
+                      f"({max_pages}), skipping posts before page {start_page}")
+                self._log_event(tid, 'check', 0, f'Skipped {skipped} page(s) over max_pages_per_check')
+            
             if verbose:
                 print(f"Fetching pages {start_page} to {end_page}...")
             
             # Fetch new pages
-            all_new_posts = []
+            fetched_pages = []
             pages_to_fetch = list(range(start_page, end_page + 1))
             
             for page_num in pages_to_fetch:
-                page_result = self.crawler.fetch_page(tid, page_num)
+                # New posts on page 1 (small threads, or overlap pages): it was fetched above
+                page_result = first_page if page_num == 1 else self.crawler.fetch_page(tid, page_num)
                 if page_result:
-                    _, posts_data = parse_page_result(page_result)
-                    all_new_posts.extend(posts_data)
+                    fetched_pages.append(page_result)
                     if verbose:
-                        print(f"  ✓ Fetched page {page_num}: {len(posts_data)} posts")
+                        print(f"  ✓ Fetched page {page_num}: {len(page_result.get('result') or [])} posts")
                 else:
                     if verbose:
                         print(f"  ✗ Failed to fetch page {page_num}")
+            all_new_posts = merge_pages(fetched_pages)
             
             # Filter posts that are actually new (by timestamp or by not existing in DB)
             # Also apply author filter
@@ -589,23 +925,19 @@ class ThreadMonitor:
             
             self.db.conn.commit()
             
-            # Send notifications for posts matching author_notification
-            author_notification = monitor_config.get('author_notification')
-            if author_notification:
-                notification_uids = set(int(uid) for uid in author_notification.split(','))
-                
-                for post in filtered_new_posts:
-                    if post['author_uid'] in notification_uids:
-                        # Send notification
-                        title = f"📬 {thread['title']}"
-                        message = f"{post['author_name']}: {post['content'][:100]}"
-                        url = f"https://bbs.nga.cn/read.php?tid={tid}&pid={post['pid']}"
-                        
-                        self.notification_manager.send(
-                            title=title,
-                            message=message,
-                            url=url
-                        )
+            # Notify about posts from author_notification authors that pass the thread's filter
+            notifies = self._notification_check(tid, monitor_config)
+            pending = [(tid, thread['title'], post, check_id) for post in filtered_new_posts if notifies(post)]
+            if verbose:
+                for _, _, post, _ in pending:
+                    print(f"  Notify: tid={tid}, pid={post['pid']}, "
+                          f"author='{post['author_name']}' (uid={post['author_uid']})")
+            
+            notifications_sent = 0
+            if notification_queue is not None:
+                notification_queue.extend(pending)
+            elif pending:
+                notifications_sent = self.flush_notifications(pending).get(tid, 0)
             
             # Display filtered new posts
             if verbose and filtered_new_posts:
@@ -624,41 +956,255 @@ class ThreadMonitor:
                 'new_posts': len(filtered_new_posts),
                 'total_new_posts': len(new_posts_to_save),
                 'total_posts': current_total_posts,
+                'notifications_sent': notifications_sent,
+                'notifications_queued': len(pending) if notification_queue is not None else 0,
                 'posts': filtered_new_posts
             }
             
         except Exception as e:
             error_msg = f'Error checking thread: {e}'
             self._log_event(tid, 'error', 0, error_msg)
+            capture_exception(e)
             if verbose:
-                print(f"\n✗ {error_msg}")
+                print(f"\n✗ {error_msg} (check {check_id})")
                 import traceback
                 traceback.print_exc()
             return {'error': error_msg}
     
+    def flush_notifications(self, pending: List[tuple]) -> Dict[int, int]:
+        """
+        Send the notifications collected during a check cycle, grouped by thread,
+        skipping each thread's disable_notifiers. With batch_notifications enabled, a thread with several new posts gets a
+        single summary notification instead of one per post.
+        
+        Args:
+            pending: (tid, thread_title, post, check_id) entries in the order they were found
+            
+        Returns:
+            Number of notifications sent per thread ID
+        """
+        by_thread: Dict[int, List[tuple]] = {}
+        for tid, thread_title, post, check_id in pending:
+            by_thread.setdefault(tid, []).append((thread_title, post, check_id))
+        
+        sent: Dict[int, int] = {}
+        for tid, entries in by_thread.items():
+            disabled = (find_monitored_thread(self.config, tid) or {}).get('disable_notifiers') or ()
+            if self.batch_notifications and len(entries) > 1:
+                notification = self.build_batch_notification(entries[0][0], [post for _, post, _ in entries])
+                self.notification_manager.send(**notification, check_id=entries[-1][2],
+                                               disable_notifiers=disabled)
+                for _, post, _ in entries:
+                    self._record_notification(post, notification)
+                sent[tid] = 1
+                continue
+            
+            for thread_title, post, check_id in entries:
+                notification = self.build_notification(thread_title, post)
+                self.notification_manager.send(**notification, check_id=check_id,
+                                               disable_notifiers=disabled)
+                self._record_notification(post, notification)
+            sent[tid] = len(entries)
+        
+        if sent:
+            self.db.conn.commit()
+        return sent
+    
+    def notification_content(self, post: Dict[str, Any], markdown: bool = False) -> str:
+        """
+        Plain-text post content for notifications.
+        Spoilers are redacted first if the thread or its filter sets redact_spoilers.
+        
+        Args:
+            post: Post dictionary from parse_page_result
+            markdown: Keep code blocks as Markdown fences instead of a placeholder
+            
+        Returns:
+            Content with BBCode stripped
+        """
+        content = post['content'] or ''
+        thread_options = find_monitored_thread(self.config, post['tid']) or {}
+        if thread_options.get('redact_spoilers') or (thread_options.get('filter') or {}).get('redact_spoilers'):
+            content = redact_spoilers(content)
+        content = format_code_blocks(content, self.code_block_max_lines, markdown)
+        return strip_bbcode(content)
+    
+    def _truncate_content(self, content: str, thread_options: Dict[str, Any]) -> str:
+        """Apply the thread's max_content_lines, max_content_sentences or max_content_chars."""
+        # Line and sentence limits take priority over the character limit
+        if thread_options.get('max_content_lines'):
+            return truncate_lines(content, thread_options['max_content_lines'])
+        if thread_options.get('max_content_sentences'):
+            return truncate_sentences(content, thread_options['max_content_sentences'])
+        return truncate_text(content, thread_options.get('max_content_chars', 100))
+    
+    def build_batch_notification(self, thread_title: str, posts: List[Dict[str, Any]]) -> Dict[str, str]:
+        """
+        Render a single notification summarizing several new posts in one thread.
+        
+        Args:
+            thread_title: Title of the thread the posts belong to
+            posts: Post dictionaries, oldest first
+            
+        Returns:
+            Dictionary with title, message and url of the first new post
+        """
+        notification = self.build_notification(thread_title, posts[0])
+        # The Markdown variant would show only the first post instead of the summary
+        notification.pop('markdown_message', None)
+        lines = [f"{post['author_name']}: {self.notification_content(post)[:50]}" for post in posts[:5]]
+        if len(posts) > 5:
+            lines.append(translate('and_more', self.locale, count=len(posts) - 5))
+        notification['message'] = translate('new_posts', self.locale, count=len(posts)) + '\n' + '\n'.join(lines)
+        return notification
+    
+    def build_notification(self, thread_title: str, post: Dict[str, Any]) -> Dict[str, str]:
+        """
+        Render the notification for a new post.
+        
+        Args:
+            thread_title: Title of the thread the post belongs to
+            post: Post dictionary from parse_page_result
+            
+        Returns:
+            Dictionary with title, message and url, plus group if the thread
+            overrides the Bark group and markdown_message if the post has code
+            blocks that Markdown senders can show
+        """
+        relative = format_relative_time(post['post_timestamp'], self.locale)
+        suffix = f" ({translate('posted', self.locale, time=relative)})" if relative else ''
+        thread_options = find_monitored_thread(self.config, post['tid']) or {}
+        content = self._truncate_content(self.notification_content(post), thread_options)
+        message = f"{post['author_name']}: {content}{suffix}"
+        
+        # The thread's first post, whoever wrote it
+        summary = ''
+        first_post = self.first_posts.get(post['tid']) if thread_options.get('include_first_post') else None
+        if first_post and first_post['pid'] != post['pid']:
+            first_content = truncate_text(self.notification_content(first_post),
+                                          thread_options.get('max_content_chars', 100))
+            summary = f"\n\n{translate('thread_summary', self.locale)}: {first_content}"
+        message += summary
+        
+        label = f"[{thread_options['note']}] " if thread_options.get('note_in_title') and thread_options.get('note') else ''
+        notification = {
+            'title': f"📬 {label}{thread_title}",
+            'message': message,
+            'url': post_url(self.crawler.web_base_url, post)
+        }
+        
+        markdown_content = self.notification_content(post, markdown=True)
+        if '```' in markdown_content:
+            markdown_content = close_code_fences(self._truncate_content(markdown_content, thread_options))
+            notification['markdown_message'] = f"{post['author_name']}: {markdown_content}{suffix}{summary}"
+        
+        # Thread-level Bark group, falls back to the global bark_group in the sender
+        if thread_options.get('bark_group'):
+            notification['group'] = thread_options['bark_group']
+        
+        return notification
+    
+    def preview_notification(self, tid: int, post_number: int) -> Dict[str, Any]:
+        """
+        Render the notification a post would produce without sending it.
+        Uses the stored post if available, otherwise fetches its page from NGA.
+        
+        Args:
+            tid: Thread ID
+            post_number: Post number (floor) within the thread
+            
+        Returns:
+            Dictionary with title, message, url and would_notify, or an error
+        """
+        post = self.db.get_post_by_number(tid, post_number)
+        thread = self.db.get_thread(tid)
+        
+        if not post or not thread:
+            # Page 1 tells the page size, which sets the page the post is on
+            page_result = self.crawler.fetch_page(tid, 1)
+            if not page_result:
+                return {'error': f'Failed to fetch page 1 of thread {tid}'}
+            page_num = post_number // thread_metadata(page_result)['per_page'] + 1
+            if page_num > 1:
+                page_result = self.crawler.fetch_page(tid, page_num)
+                if not page_result:
+                    return {'error': f'Failed to fetch page {page_num} of thread {tid}'}
+            
+            thread, posts_data = parse_page_result(page_result)
+            post = next((p for p in posts_data if p['post_number'] == post_number), None)
+            if not post:
+                return {'error': f'Post #{post_number} not found in thread {tid}'}
+        
+        # Apply the same notification filter as check_thread
+        self.db.cursor.execute(
+            'SELECT * FROM monitored_threads WHERE tid = ? AND is_active = 1',
+            (tid,)
+        )
+        row = self.db.cursor.fetchone()
+        
+        preview = self.build_notification(thread['title'], post)
+        preview['would_notify'] = bool(row) and self._notification_check(tid, dict(row))(post)
+        return preview
+    
+    def _notification_check(self, tid: int, monitor_config: Dict[str, Any]) -> Callable[[Dict[str, Any]], bool]:
+        """
+        Whether a post of a monitored thread triggers a notification, as decided by
+        check_thread and preview_notification: the author passes author_filter, and
+        thread_matches_post accepts it with the thread's notification settings.
+        
+        Args:
+            tid: Thread ID
+            monitor_config: The thread's monitored_threads row
+        """
+        author_filter = monitor_config.get('author_filter')
+        author_uids = set(int(uid) for uid in author_filter.split(',')) if author_filter else set()
+        author_notification = monitor_config.get('author_notification')
+        notification_uids = set(int(uid) for uid in author_notification.split(',')) if author_notification else set()
+        thread_options = find_monitored_thread(self.config, tid) or {}
+        post_filter = PostFilter.from_config(thread_options.get('filter'))
+        excluded_uids = set(thread_options.get('author_exclusion') or [])
+        rules = thread_options.get('notification_rules') or []
+        
+        def notifies(post: Dict[str, Any]) -> bool:
+            if author_uids and post['author_uid'] not in author_uids:
+                return False
+            return thread_matches_post(post, notification_uids, post_filter, excluded_uids, rules)
+        
+        return notifies
+    
     def check_all(self, verbose: bool = True) -> Dict[str, Any]:
         """
-        Check all active monitored threads.
+        Check all active monitored threads once, e.g. for `monitor.py check`.
+        Snoozed threads are skipped.
         
         Args:
             verbose: Print detailed output
             
         Returns:
-            Summary of checks
+            Summary of checks, with the outcome per thread ID under threads:
+            max_post_number (highest stored post number), notifications_sent and error
         """
         monitored = self.list_monitored()
         
         if not monitored:
             print("No threads being monitored")
-            return {'total': 0, 'checked': 0, 'new_posts': 0}
+            return {'total': 0, 'checked': 0, 'new_posts': 0, 'threads': {}}
         
         print(f"\nChecking {len(monitored)} monitored thread(s)...")
         
         total_new = 0
         checked = 0
+        queue: List[tuple] = []
+        results = []
         
         for thread in monitored:
-            result = self.check_thread(thread['tid'], verbose=verbose)
+            if self.is_snoozed(thread['tid']):
+                print(f"Skipping {self.describe_thread(thread['tid'], thread['title'])}: snoozed until "
+                      f"{self.thread_states[thread['tid']].snooze_until:%Y-%m-%d %H:%M:%S}")
+                continue
+            result = self.check_thread(thread['tid'], verbose=verbose, monitor_config=thread,
+                                       notification_queue=queue)
+            results.append((thread, result))
             if 'new_posts' in result:
                 total_new += result['new_posts']
                 checked += 1
@@ -666,6 +1212,18 @@ class ThreadMonitor:
             # Small delay between checks
             time.sleep(1)
         
+        sent = self.flush_notifications(queue)
+        threads = {}
+        for thread, result in results:
+            if 'error' not in result:
+                result['notifications_sent'] = sent.get(thread['tid'], 0)
+            self.update_thread_state(thread['tid'], result, thread['check_interval'])
+            threads[thread['tid']] = {
+                'max_post_number': self._max_post_number(thread['tid']),
+                'notifications_sent': result.get('notifications_sent', 0),
+                'error': result.get('error')
+            }
+        
         print(f"\n{'='*80}")
         print(f"Summary: Checked {checked} threads, found {total_new} new post(s)")
         print(f"{'='*80}")
@@ -673,10 +1231,12 @@ class ThreadMonitor:
         return {
             'total': len(monitored),
             'checked': checked,
-            'new_posts': total_new
+            'new_posts': total_new,
+            'threads': threads
         }
     
-    def run_loop(self, check_all_interval: int = 10, stop_event=None):
+    def run_loop(self, check_all_interval: int = 10, stop_event=None, ready_event=None,
+                 reload_event=None):
         """
         Run monitoring loop continuously.
         Respects per-thread check_interval from database.
@@ -684,6 +1244,9 @@ class ThreadMonitor:
         Args:
             check_all_interval: Seconds between checking which threads need updates (default: 10)
             stop_event: Optional threading.Event to signal loop to stop
+            ready_event: Optional threading.Event set once the first cycle has completed
+            reload_event: Optional threading.Event; when set, config.json is reloaded
+                before the next cycle
         """
         print(f"Starting monitoring loop")
         print("Each thread will be checked according to its own check_interval")
@@ -698,11 +1261,24 @@ class ThreadMonitor:
                 if stop_event and stop_event.is_set():
                     print("\nMonitoring loop stopped by signal")
                     break
+                
+                if reload_event and reload_event.is_set():
+                    reload_event.clear()
+                    self.reload_config(stop_event=stop_event)
+                
+                if self.credentials_expired:
+                    if ready_event:
+                        ready_event.set()
+                    time.sleep(check_all_interval)
+                    continue
+                
                 # Get all monitored threads with their configuration
                 monitored = self.list_monitored()
                 
                 if not monitored:
                     print("No threads being monitored. Waiting...")
+                    if ready_event:
+                        ready_event.set()
                     time.sleep(check_all_interval)
                     continue
                 
@@ -713,30 +1289,25 @@ class ThreadMonitor:
                 for thread in monitored:
                     tid = thread['tid']
                     check_interval = thread['check_interval']
-                    last_checked = thread['last_checked']
+                    # check_interval 0: only checked through POST /api/v1/threads/{tid}/check
+                    if check_interval == 0:
+                        continue
+                    state = self.thread_states.get(tid)
+                    if state and state.consecutive_errors > 0:
+                        check_interval = state.effective_interval or check_interval
                     
-                    # Parse last_checked time
-                    if last_checked:
-                        try:
-                            # SQLite datetime format: 'YYYY-MM-DD HH:MM:SS'
-                            from datetime import datetime
-                            last_check_dt = datetime.strptime(last_checked, '%Y-%m-%d %H:%M:%S')
-                            last_check_timestamp = last_check_dt.timestamp()
-                            time_since_check = current_time - last_check_timestamp
-                        except:
-                            # If parsing fails, check immediately
-                            time_since_check = check_interval + 1
-                    else:
-                        # Never checked, check immediately
-                        time_since_check = check_interval + 1
+                    # Never checked (or unparsable last_checked): check immediately
+                    next_check = next_check_time(thread['last_checked'], thread['check_interval'], state)
+                    overdue_by = current_time - next_check.timestamp() if next_check else 0
                     
                     # Should we check this thread?
-                    if time_since_check >= check_interval:
+                    if overdue_by >= 0:
                         threads_to_check.append({
                             'tid': tid,
                             'title': thread['title'],
                             'check_interval': check_interval,
-                            'overdue_by': time_since_check - check_interval
+                            'overdue_by': overdue_by,
+                            'monitor_config': thread
                         })
                 
                 # Check threads that are due
@@ -745,37 +1316,76 @@ class ThreadMonitor:
                     print(f"Checking {len(threads_to_check)} thread(s) due for update")
                     print(f"{'='*80}")
                     
+                    queue: List[tuple] = []
+                    results = []
+                    cycle_deadline = time.monotonic() + self.max_cycle_secs
                     for thread_info in threads_to_check:
-                        print(f"\nThread {thread_info['tid']}: {thread_info['title']}")
+                        # Posts found so far are already saved, so their notifications
+                        # must still go out: skip the remaining threads, not the dispatch
+                        if stop_event and stop_event.is_set():
+                            print("\nStop requested, sending queued notifications before exiting")
+                            break
+                        
+                        # Slow or hanging requests mustn't stall the loop: the rest stay due
+                        if self.max_cycle_secs and time.monotonic() > cycle_deadline:
+                            skipped = len(threads_to_check) - len(results)
+                            print(f"\n✗ Check cycle exceeded max_cycle_secs ({self.max_cycle_secs}s), "
+                                  f"{skipped} thread(s) postponed to the next cycle")
+                            capture_message(f'Check cycle exceeded {self.max_cycle_secs}s')
+                            break
+                        
+                        print(f"\n{self.describe_thread(thread_info['tid'], thread_info['title'])}")
                         print(f"  Check interval: {thread_info['check_interval']}s")
                         if thread_info['overdue_by'] > 0:
                             print(f"  Overdue by: {thread_info['overdue_by']:.0f}s")
                         
-                        self.check_thread(thread_info['tid'], verbose=True)
+                        result = self.check_thread(thread_info['tid'], verbose=True,
+                                                   monitor_config=thread_info['monitor_config'],
+                                                   notification_queue=queue)
+                        results.append((thread_info, result))
                         
                         # Small delay between threads
                         time.sleep(1)
                     
+                    # Dispatch this cycle's notifications together, grouped by thread
+                    sent = self.flush_notifications(queue)
+                    for thread_info, result in results:
+                        if 'error' not in result:
+                            result['notifications_sent'] = sent.get(thread_info['tid'], 0)
+                        state = self.update_thread_state(
+                            thread_info['tid'], result,
+                            thread_info['monitor_config']['check_interval']
+                        )
+                        if state.effective_interval:
+                            print(f"  ⚠ TID {thread_info['tid']}: {state.consecutive_errors} consecutive error(s), "
+                                  f"next attempt in {state.effective_interval}s")
+                    
                     print(f"\n{'='*80}")
-                    print(f"Completed checking {len(threads_to_check)} thread(s)")
+                    print(f"Completed checking {len(results)} thread(s)")
                     print(f"{'='*80}")
                 
                 # Show status of all threads
                 print(f"\nMonitoring {len(monitored)} thread(s):")
                 for thread in monitored:
                     last_checked = thread['last_checked'] or 'Never'
-                    print(f"  TID {thread['tid']}: {thread['title']}")
-                    print(f"    Interval: {thread['check_interval']}s, Last checked: {last_checked}")
+                    print(f"  {self.describe_thread(thread['tid'], thread['title'])}")
+                    interval = f"{thread['check_interval']}s" if thread['check_interval'] else 'on demand'
+                    print(f"    Interval: {interval}, Last checked: {last_checked}")
+                
+                if ready_event:
+                    ready_event.set()
                 
                 # Wait before next check cycle
                 print(f"\nWaiting {check_all_interval}s until next evaluation...")
                 
-                if stop_event:
-                    # Sleep in small chunks to respond quickly to stop signal
+                if stop_event or reload_event:
+                    # Sleep in small chunks to respond quickly to stop and reload signals
                     for _ in range(check_all_interval):
-                        if stop_event.is_set():
+                        if stop_event and stop_event.is_set():
                             print("\nMonitoring loop stopped by signal")
                             return
+                        if reload_event and reload_event.is_set():
+                            break
                         time.sleep(1)
                 else:
                     time.sleep(check_all_interval)
@@ -783,6 +1393,137 @@ class ThreadMonitor:
         except KeyboardInterrupt:
             print("\n\nMonitoring stopped by user")
     
+    def snooze_thread(self, tid: int, duration_secs: int) -> datetime:
+        """
+        Skip scheduled checks and check_all runs of a thread for duration_secs.
+        Checking resumes by itself afterwards; checks of just this thread still run.
+        
+        Returns:
+            End of the snooze
+        """
+        state = self.thread_states.setdefault(tid, ThreadCheckState())
+        state.snooze_until = datetime.now() + timedelta(seconds=duration_secs)
+        return state.snooze_until
+    
+    def is_snoozed(self, tid: int) -> bool:
+        """Whether a thread has a snooze that hasn't ended yet."""
+        state = self.thread_states.get(tid)
+        return bool(state and state.snooze_until and state.snooze_until > datetime.now())
+    
+    def cancel_snooze(self, tid: int) -> bool:
+        """
+        End a thread's snooze early.
+        
+        Returns:
+            True if the thread was snoozed
+        """
+        if not self.is_snoozed(tid):
+            return False
+        self.thread_states[tid].snooze_until = None
+        return True
+    
+    def update_thread_state(self, tid: int, result: Dict[str, Any],
+                             check_interval: int) -> ThreadCheckState:
+        """
+        Record the outcome of a check_thread call in the thread's runtime state.
+        
+        Args:
+            tid: Thread ID
+            result: Dictionary returned by check_thread
+            check_interval: Configured check interval of the thread in seconds
+            
+        Returns:
+            Updated thread state
+        """
+        state = self.thread_states.setdefault(tid, ThreadCheckState())
+        state.last_checked_at = datetime.now()
+        
+        state.history.append(CheckHistoryEntry(
+            checked_at=state.last_checked_at,
+            posts_found=result.get('total_new_posts', 0),
+            new_notifications_sent=result.get('notifications_sent', 0),
+            error=result.get('error'),
+            duration_ms=result.get('duration_ms', 0),
+            check_id=result.get('check_id')
+        ))
+        while len(state.history) > max(self.check_history_size, 0):
+            state.history.popleft()
+        
+        state.retry_at = None
+        if result.get('retry'):
+            # Rate limited: not counted as a failure, the thread is retried once the wait is over
+            state.last_error = result['error']
+            state.retry_at = state.last_checked_at + timedelta(seconds=result.get('retry_after_secs', 0))
+        elif 'error' in result:
+            state.consecutive_errors += 1
+            state.last_error = result['error']
+            backoff = check_interval * self.error_backoff_factor ** state.consecutive_errors
+            state.effective_interval = int(min(backoff, self.max_error_backoff_secs))
+            self._maybe_alert_failure(tid, state)
+        else:
+            state.consecutive_errors = 0
+            state.last_error = None
+            state.effective_interval = None
+            if result.get('new_posts', 0) > 0:
+                state.no_new_posts_streak = 0
+            else:
+                state.no_new_posts_streak += 1
+            state.total_notifications += result.get('notifications_sent', 0)
+        
+        return state
+    
+    def _maybe_alert_failure(self, tid: int, state: ThreadCheckState):
+        """
+        Send a notification when a thread has failed error_alert_threshold times in a row.
+        Each failure streak alerts once, when it reaches the threshold; alerts for a
+        thread that keeps recovering and failing again are throttled to one per
+        error_alert_repeat_hours.
+        
+        Args:
+            tid: Thread ID
+            state: Runtime state of the thread
+        """
+        if not self.error_alert_threshold or state.consecutive_errors != self.error_alert_threshold:
+            return
+        
+        now = datetime.now()
+        if state.last_error_alert_at:
+            hours_since_alert = (now - state.last_error_alert_at).total_seconds() / 3600
+            if hours_since_alert < self.error_alert_repeat_hours:
+                return
+        
+        self.notification_manager.send(
+            title=translate('check_failing_title', self.locale),
+            message=translate('check_failing_message', self.locale, tid=tid,
+                              count=state.consecutive_errors, error=state.last_error),
+            url=thread_url(self.crawler.web_base_url, tid)
+        )
+        state.last_error_alert_at = now
+    
+    def _pause_for_credentials(self):
+        """
+        Stop checking threads after NGA reports that the cookies are no longer
+        logged in, and notify once. Monitoring resumes on config reload.
+        """
+        if self.credentials_expired:
+            return
+        self.credentials_expired = True
+        print("✗ NGA credentials expired, pausing monitoring until the config is reloaded")
+        self.notification_manager.send(
+            title=translate('credentials_expired_title', self.locale),
+            message=translate('credentials_expired_message', self.locale),
+            url="https://bbs.nga.cn/"
+        )
+    
+    def _max_post_number(self, tid: int) -> Optional[int]:
+        """Highest post number stored for a thread, None if it has no posts."""
+        self.db.cursor.execute('SELECT MAX(post_number) FROM posts WHERE tid = ?', (tid,))
+        return self.db.cursor.fetchone()[0]
+    
+    def describe_thread(self, tid: int, title: Optional[str] = None) -> str:
+        """describe_thread with the thread's note from config.json."""
+        return describe_thread(tid, (find_monitored_thread(self.config, tid) or {}).get('note'), title)
+    
     def _log_event(self, tid: int, event_type: str, post_count: int, message: str):
         """Log a monitoring event."""
         self.db.cursor.execute('''
@@ -790,6 +1531,84 @@ class ThreadMonitor:
             VALUES (?, ?, ?, ?)
         ''', (tid, event_type, post_count, message))
     
+    def _record_notification(self, post: Dict[str, Any], notification: Dict[str, str]):
+        """Add a sent notification to the notification history."""
+        self.db.cursor.execute('''
+            INSERT INTO notification_history (tid, pid, author_uid, author_name, title, message, url)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+        ''', (post['tid'], post['pid'], post['author_uid'], post['author_name'],
+              notification['title'], notification['message'], notification.get('url')))
+    
+    def get_notification_counts(self) -> Dict[int, int]:
+        """
+        Number of notifications sent per thread since its stats were last reset.
+        
+        Returns:
+            Mapping of tid to count; threads without notifications are left out
+        """
+        self.db.cursor.execute('''
+            SELECT h.tid, COUNT(*) FROM notification_history h
+            LEFT JOIN notification_stats_resets r ON r.tid = h.tid
+            WHERE r.last_history_id IS NULL OR h.id > r.last_history_id
+            GROUP BY h.tid
+        ''')
+        return {row[0]: row[1] for row in self.db.cursor.fetchall()}
+    
+    def reset_notification_count(self, tid: int):
+        """Restart a thread's notification count from zero. The history itself is kept."""
+        self.db.cursor.execute('''
+            INSERT INTO notification_stats_resets (tid, last_history_id)
+            VALUES (?, (SELECT COALESCE(MAX(id), 0) FROM notification_history))
+            ON CONFLICT(tid) DO UPDATE SET
+                last_history_id = excluded.last_history_id,
+                reset_at = datetime('now', 'localtime')
+        ''', (tid,))
+        self.db.conn.commit()
+    
+    def get_notification_history(self, limit: int = 50, offset: int = 0, tid: Optional[int] = None,
+                                 author: Optional[str] = None) -> Dict[str, Any]:
+        """
+        Get sent notifications, newest first.
+        
+        Args:
+            limit: Maximum number of entries to return
+            offset: Number of entries to skip
+            tid: Optional thread ID filter
+            author: Optional author filter, a UID or an exact author name
+            
+        Returns:
+            Dictionary with the total number of matching entries and the requested page
+        """
+        conditions = []
+        params: List[Any] = []
+        if tid:
+            conditions.append('tid = ?')
+            params.append(tid)
+        if author:
+            if author.isdigit():
+                conditions.append('author_uid = ?')
+                params.append(int(author))
+            else:
+                conditions.append('author_name = ?')
+                params.append(author)
+        where = f"WHERE {' AND '.join(conditions)}" if conditions else ''
+        
+        self.db.cursor.execute(f'SELECT COUNT(*) FROM notification_history {where}', params)
+        total = self.db.cursor.fetchone()[0]
+        
+        self.db.cursor.execute(f'''
+            SELECT * FROM notification_history {where}
+            ORDER BY sent_at DESC, id DESC
+            LIMIT ? OFFSET ?
+        ''', params + [limit, offset])
+        
+        return {
+            'total': total,
+            'limit': limit,
+            'offset': offset,
+            'items': [dict(row) for row in self.db.cursor.fetchall()]
+        }
+    
     def get_events(self, tid: Optional[int] = None, limit: int = 50) -> List[Dict[str, Any]]:
         """Get monitoring event history."""
         if tid:
@@ -812,6 +1631,16 @@ class ThreadMonitor:
         
         return [dict(row) for row in self.db.cursor.fetchall()]
     
+    def with_own_connection(self) -> 'ThreadMonitor':
+        """
+        A copy of this monitor with its own database connection, for use from another
+        thread. Config, crawler, notification senders and thread states stay shared, so
+        nothing is reloaded or rebuilt. Close the copy when done; this monitor stays open.
+        """
+        other = copy.copy(self)
+        other.db = NGADatabase(self.db.db_path)
+        return other
+    
     def close(self):
         """Close database connection."""
         self.db.close()
@@ -824,6 +1653,9 @@ def main():
         formatter_class=argparse.RawDescriptionHelpFormatter
     )
     
+    parser.add_argument('--config', dest='config_file', type=str,
+                        help='Config file path (default: $NGA_CONFIG_FILE or config/config.json)')
+    
     subparsers = parser.add_subparsers(dest='command', help='Commands')
     
     # Add thread
@@ -839,6 +1671,12 @@ def main():
     # List monitored threads
     subparsers.add_parser('list', help='List monitored threads')
     
+    # List threads from config
+    list_threads_parser = subparsers.add_parser('list-threads', help='List threads configured in config.json')
+    list_threads_parser.add_argument('--no-fetch', action='store_true',
+                                     help="Don't fetch titles of threads not in the database yet")
+    list_threads_parser.add_argument('--format', choices=['table', 'json'], default='table', help='Output format')
+    
     # Check thread once
     check_parser = subparsers.add_parser('check', help='Check thread for new posts')
     check_parser.add_argument('--tid', type=int, help='Thread ID (omit to check all)')
@@ -855,7 +1693,7 @@ def main():
     
     # Sync from config
     sync_parser = subparsers.add_parser('sync', help='Load monitored threads from config file')
-    sync_parser.add_argument('--config', type=str, default='config.json', help='Config file path')
+    sync_parser.add_argument('--config', type=str, help='Config file to sync from (default: the monitor config)')
     
     args = parser.parse_args()
     
@@ -863,7 +1701,21 @@ def main():
         parser.print_help()
         return
     
-    monitor = ThreadMonitor()
+    config_path = args.config_file or DEFAULT_CONFIG_PATH
+    source = '--config' if args.config_file else 'NGA_CONFIG_FILE' if os.environ.get('NGA_CONFIG_FILE') else 'default'
+    # stderr keeps machine-readable output (list-threads --format json) clean
+    print(f"Using config file {config_path} ({source})", file=sys.stderr)
+    try:
+        create_config_from_env(config_path)
+    except (ValueError, OSError) as e:
+        print(f"Error: {e}", file=sys.stderr)
+        sys.exit(1)
+    try:
+        log_schema_version(load_config(config_path, migrate=False), file=sys.stderr)
+    except (OSError, json.JSONDecodeError):
+        # ThreadMonitor reports the missing or broken file
+        pass
+    monitor = ThreadMonitor(config_path=config_path)
     
     try:
         if args.command == 'add':
@@ -878,7 +1730,7 @@ def main():
             if threads:
                 print(f"\nMonitored threads ({len(threads)}):\n")
                 for t in threads:
-                    print(f"  TID {t['tid']}: {t['title']}")
+                    print(f"  {monitor.describe_thread(t['tid'], t['title'])}")
                     print(f"    Author: {t['author_name']}")
                     print(f"    Filter: {t['author_filter'] or 'All authors'}")
                     print(f"    Interval: {t['check_interval']}s")
@@ -887,6 +1739,20 @@ def main():
             else:
                 print("\nNo threads being monitored")
         
+        elif args.command == 'list-threads':
+            threads = monitor.list_config_threads(fetch_titles=not args.no_fetch)
+            if args.format == 'json':
+                print(json.dumps(threads, ensure_ascii=False, indent=2))
+            elif threads:
+                print(f"{'TID':<10} {'ENABLED':<8} {'LAST POST':<10} {'INTERVAL':<9} TITLE")
+                for t in threads:
+                    last_seen = t['last_seen_post_number'] if t['last_seen_post_number'] is not None else '-'
+                    title = t['title'] if len(t['title']) <= 40 else t['title'][:39] + '…'
+                    print(f"{t['tid']:<10} {'yes' if t['enabled'] else 'no':<8} {last_seen:<10} "
+                          f"{str(t['check_interval']) + 's':<9} {title}")
+            else:
+                print("\nNo threads configured")
+        
         elif args.command == 'check':
             if args.tid:
                 monitor.check_thread(args.tid)
@@ -894,7 +1760,10 @@ def main():
                 monitor.check_all()
         
         elif args.command == 'loop':
-            monitor.run_loop(args.check_all_interval)
+            reload_event = threading.Event()
+            if hasattr(signal, 'SIGHUP'):
+                signal.signal(signal.SIGHUP, lambda signum, frame: reload_event.set())
+            monitor.run_loop(args.check_all_interval, reload_event=reload_event)
         
         elif args.command == 'events':
             events = monitor.get_events(args.tid, args.limit)
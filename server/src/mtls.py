@@ -0,0 +1,81 @@
+#!/usr/bin/env python3
+"""
+Client certificate support for mutual TLS (server_mtls_ca_certs).
+
+The TLS handshake only verifies certificates that are presented; whether one is
+required is decided per request by the API, so /health and /live work without one.
+ClientCertProtocol passes the certificate subject to the app as the ASGI TLS
+extension (scope["extensions"]["tls"]["client_cert_name"]), which uvicorn doesn't.
+"""
+
+import re
+from typing import Any, Dict, Optional
+
+try:
+    from uvicorn.protocols.http.httptools_impl import HttpToolsProtocol as _BaseProtocol
+except ImportError:
+    try:
+        from uvicorn.protocols.http.h11_impl import H11Protocol as _BaseProtocol
+    except ImportError:
+        _BaseProtocol = None
+
+# Short attribute names of RFC 4514 distinguished names, by ssl.getpeercert() name
+_DN_ATTRIBUTES = {
+    'commonName': 'CN',
+    'organizationName': 'O',
+    'organizationalUnitName': 'OU',
+    'countryName': 'C',
+    'stateOrProvinceName': 'ST',
+    'localityName': 'L'
+}
+
+_CN_RE = re.compile(r'(?:^|,)CN=((?:[^,\\]|\\.)*)')
+
+
+def _escape(value: str) -> str:
+    """Escape the characters that separate DN attributes with a backslash."""
+    return re.sub(r'([,+=\\])', r'\\\1', value)
+
+
+def client_cert_name(cert: Optional[Dict[str, Any]]) -> Optional[str]:
+    """
+    Distinguished name of a verified client certificate.
+
+    Args:
+        cert: Result of SSLSocket.getpeercert(), None or empty without a certificate
+
+    Returns:
+        e.g. "CN=ops-laptop,O=Example", or None
+    """
+    if not cert or not cert.get('subject'):
+        return None
+    return ','.join(f"{_DN_ATTRIBUTES.get(key, key)}={_escape(value)}"
+                    for rdn in cert['subject'] for key, value in rdn)
+
+
+def common_name(name: Optional[str]) -> Optional[str]:
+    """
+    CN of a distinguished name from client_cert_name.
+
+    Returns:
+        The common name, or None if name is None or has no CN
+    """
+    match = _CN_RE.search(name or '')
+    return re.sub(r'\\(.)', r'\1', match.group(1)) if match else None
+
+
+if _BaseProtocol is not None:
+    class ClientCertProtocol(_BaseProtocol):
+        """uvicorn HTTP protocol adding the connection's client certificate to each request scope."""
+
+        def connection_made(self, transport):
+            super().connection_made(transport)
+            ssl_object = transport.get_extra_info('ssl_object')
+            name = client_cert_name(ssl_object.getpeercert()) if ssl_object else None
+            app = self.app
+
+            async def app_with_client_cert(scope, receive, send):
+                scope.setdefault('extensions', {})['tls'] = {'client_cert_name': name}
+                await app(scope, receive, send)
+
+            self.app = app_with_client_cert
@@ -3,14 +3,70 @@
 Notification interface and implementations for NGA monitor.
 """
 
+import re
+import threading
 from abc import ABC, abstractmethod
-from typing import Dict, Any, List
+from contextlib import nullcontext
+from datetime import datetime
+from typing import Collection, Dict, Any, List, Optional, Tuple
+from urllib.parse import quote
 import requests
+from . import schedule
+from .i18n import DEFAULT_LOCALE, translate
+from .util import truncate_text
+
+
+# Names of the built-in senders, the prefixes of their config keys
+SENDER_NAMES = ('bark', 'pushbullet', 'slack', 'console')
+
+# Semaphores for max_concurrent_notifications, shared by every NotificationManager in the
+# process (the monitor loop, API checks, replays, ...) so the limit holds across all of them
+_send_semaphores: Dict[int, threading.Semaphore] = {}
+_send_semaphores_lock = threading.Lock()
+
+
+def _send_semaphore(limit: Optional[int]) -> Optional[threading.Semaphore]:
+    """The process-wide semaphore for a max_concurrent_notifications limit, None for unlimited."""
+    if not limit:
+        return None
+    with _send_semaphores_lock:
+        return _send_semaphores.setdefault(limit, threading.Semaphore(limit))
+
+
+# Bark device keys are letters and digits; - and _ are allowed for self-hosted servers
+_BARK_DEVICE_KEY_RE = re.compile(r'[A-Za-z0-9_-]+')
 
 
 class NotificationSender(ABC):
     """Abstract base class for notification senders."""
     
+    # Config key prefix, e.g. "bark" for bark_enabled and bark_schedule
+    name = ''
+    
+    # Time windows in which the sender is active (None for always)
+    enabled_schedule: Optional[List[Dict[str, Any]]] = None
+    
+    # Timezone of windows without their own, from the top-level timezone setting
+    schedule_timezone: Optional[str] = None
+    
+    # Whether the sender renders Markdown and gets markdown_message when available
+    supports_markdown = False
+    
+    # Delivery health, updated by NotificationManager.send
+    consecutive_failures = 0
+    last_success_at: Optional[datetime] = None
+    last_failure_at: Optional[datetime] = None
+    
+    # Attributes holding credentials, shown as [redacted] by __repr__
+    secret_attributes: Tuple[str, ...] = ()
+    
+    def __repr__(self) -> str:
+        fields = ', '.join(
+            f"{key}='[redacted]'" if key in self.secret_attributes and value else f"{key}={value!r}"
+            for key, value in vars(self).items()
+        )
+        return f"{type(self).__name__}({fields})"
+    
     @abstractmethod
     def send(self, title: str, message: str, **kwargs) -> bool:
         """
@@ -35,11 +91,23 @@ class NotificationSender(ABC):
             True if configured and ready to send
         """
         pass
+    
+    def is_active(self, now: Optional[datetime] = None) -> bool:
+        """
+        Check if the sender's enabled_schedule allows sending now.
+        
+        Returns:
+            True if the sender has no schedule or is inside one of its windows
+        """
+        return schedule.is_active(self.enabled_schedule, now, self.schedule_timezone)
 
 
 class BarkNotificationSender(NotificationSender):
     """Bark notification sender implementation."""
     
+    name = 'bark'
+    secret_attributes = ('device_key',)
+    
     def __init__(self, config: Dict[str, Any]):
         """
         Initialize Bark sender.
@@ -52,11 +120,46 @@ class BarkNotificationSender(NotificationSender):
         self.sound = config.get('bark_sound', 'default')
         self.group = config.get('bark_group', 'NGA')
         self.icon = config.get('bark_icon', '')
+        self.save = config.get('bark_save', False)
+        self.is_archive = config.get('bark_is_archive', False)
+        self.auto_copy = config.get('bark_auto_copy', False)
         self.timeout = config.get('bark_timeout', 10)
+        # 1: GET /<device_key>?title=...; 2: POST /push with a JSON body including the key
+        self.api_version = config.get('bark_api_version', 1)
+        # Push URL for Bark-compatible servers with another layout, e.g. "{server_url}/push"
+        self.url_template = config.get('bark_url_template')
+        
+        # A key with other characters would end up as a broken URL path
+        self.invalid_device_key = bool(self.device_key) and not _BARK_DEVICE_KEY_RE.fullmatch(self.device_key)
+        if self.invalid_device_key:
+            print("Warning: bark_device_key contains characters other than letters, digits, - and _; "
+                  "Bark notifications are disabled")
+        self.invalid_url_template = bool(self.url_template) and not is_valid_bark_url_template(self.url_template)
+        if self.invalid_url_template:
+            print("Warning: bark_url_template must contain {server_url} and no other placeholders than "
+                  "{device_key}, {title} and {body}; Bark notifications are disabled")
     
     def is_configured(self) -> bool:
-        """Check if Bark is configured."""
-        return bool(self.server_url and self.device_key)
+        """Check if Bark is configured with a valid device key and URL template."""
+        return (bool(self.server_url and self.device_key) and not self.invalid_device_key
+                and not self.invalid_url_template)
+    
+    def api_url(self, title: str = '', body: str = '') -> str:
+        """
+        Push endpoint for the device, e.g. https://api.day.app/<device_key>, or
+        https://api.day.app/push with bark_api_version 2.
+        server_url may end with a slash or not, and may contain a path
+        (e.g. https://example.com/bark for a self-hosted server behind a proxy).
+        With bark_url_template, the template filled in with server_url and the
+        percent-encoded device key, title and body.
+        """
+        if self.url_template:
+            return self.url_template.format(server_url=self.server_url.rstrip('/'),
+                                            device_key=quote(self.device_key, safe=''),
+                                            title=quote(title, safe=''), body=quote(body, safe=''))
+        if self.api_version == 2:
+            return f"{self.server_url.rstrip('/')}/push"
+        return f"{self.server_url.rstrip('/')}/{self.device_key}"
     
     def send(self, title: str, message: str, **kwargs) -> bool:
         """
@@ -70,19 +173,21 @@ class BarkNotificationSender(NotificationSender):
                 - sound: Override default sound
                 - group: Override default group
                 - icon: Override default icon
+                - bark_save: Override bark_save (archive in the Bark app)
+                - bark_is_archive: Override bark_is_archive (keep in notification center)
+                - bark_auto_copy: Override bark_auto_copy (copy content to clipboard)
                 
         Returns:
             True if sent successfully
         """
+        if self.invalid_device_key:
+            print("Invalid bark_device_key, skipping notification")
+            return False
         if not self.is_configured():
             print("Bark not configured, skipping notification")
             return False
         
         try:
-            # Build Bark API URL
-            # Format: http://server/device_key/title/message
-            api_url = f"{self.server_url.rstrip('/')}/{self.device_key}"
-            
             # Prepare parameters
             params = {
                 'title': title,
@@ -99,8 +204,20 @@ class BarkNotificationSender(NotificationSender):
             if icon:
                 params['icon'] = icon
             
+            if kwargs.get('bark_save', self.save):
+                params['save'] = 1
+            if kwargs.get('bark_is_archive', self.is_archive):
+                params['isArchive'] = 1
+            if kwargs.get('bark_auto_copy', self.auto_copy):
+                params['autoCopy'] = 1
+            
             # Send request
-            response = requests.get(api_url, params=params, timeout=self.timeout)
+            if self.api_version == 2:
+                response = requests.post(self.api_url(title, message),
+                                         json={'device_key': self.device_key, **params},
+                                         timeout=self.timeout)
+            else:
+                response = requests.get(self.api_url(title, message), params=params, timeout=self.timeout)
             response.raise_for_status()
             
             # Check response
@@ -119,12 +236,190 @@ class BarkNotificationSender(NotificationSender):
             return False
 
 
+def is_valid_bark_url_template(template: Any) -> bool:
+    """Check that a bark_url_template has {server_url} and only known placeholders."""
+    if not isinstance(template, str) or '{server_url}' not in template:
+        return False
+    try:
+        template.format(server_url='', device_key='', title='', body='')
+    except (KeyError, IndexError, ValueError):
+        return False
+    return True
+
+
+class PushbulletNotificationSender(NotificationSender):
+    """Pushbullet notification sender implementation."""
+    
+    name = 'pushbullet'
+    secret_attributes = ('api_token',)
+    
+    API_URL = 'https://api.pushbullet.com/v2/pushes'
+    
+    def __init__(self, config: Dict[str, Any]):
+        """
+        Initialize Pushbullet sender.
+        
+        Args:
+            config: Configuration dictionary with pushbullet settings
+        """
+        self.api_token = config.get('pushbullet_api_token', '')
+        self.device_iden = config.get('pushbullet_device_iden')
+        self.channel_tag = config.get('pushbullet_channel_tag')
+        self.timeout = config.get('pushbullet_timeout', 10)
+    
+    def is_configured(self) -> bool:
+        """Check if Pushbullet is configured."""
+        return bool(self.api_token)
+    
+    def send(self, title: str, message: str, **kwargs) -> bool:
+        """
+        Send notification via Pushbullet as a link push.
+        Pushed to the channel if pushbullet_channel_tag is set, otherwise to
+        pushbullet_device_iden, otherwise to all of the user's devices.
+        
+        Args:
+            title: Notification title
+            message: Notification message
+            **kwargs: Optional parameters:
+                - url: URL to open when notification is tapped
+                
+        Returns:
+            True if sent successfully
+        """
+        if not self.is_configured():
+            print("Pushbullet not configured, skipping notification")
+            return False
+        
+        payload = {
+            'type': 'link',
+            'title': title,
+            'body': message
+        }
+        if kwargs.get('url'):
+            payload['url'] = kwargs['url']
+        
+        if self.channel_tag:
+            payload['channel_tag'] = self.channel_tag
+        elif self.device_iden:
+            payload['device_iden'] = self.device_iden
+        
+        try:
+            response = requests.post(
+                self.API_URL,
+                json=payload,
+                headers={'Authorization': f'Bearer {self.api_token}'},
+                timeout=self.timeout
+            )
+            response.raise_for_status()
+            return True
+        except requests.exceptions.RequestException as e:
+            print(f"Failed to send Pushbullet notification: {e}")
+            return False
+        except Exception as e:
+            print(f"Error sending Pushbullet notification: {e}")
+            return False
+
+
+class SlackNotificationSender(NotificationSender):
+    """Slack incoming webhook sender using Block Kit messages."""
+    
+    name = 'slack'
+    secret_attributes = ('webhook_url',)
+    supports_markdown = True
+    
+    WEBHOOK_PREFIX = 'https://hooks.slack.com/'
+    
+    def __init__(self, config: Dict[str, Any]):
+        """
+        Initialize Slack sender.
+        
+        Args:
+            config: Configuration dictionary with slack settings
+        """
+        self.webhook_url = config.get('slack_webhook_url', '')
+        self.username = config.get('slack_username')
+        self.locale = config.get('notification_locale', DEFAULT_LOCALE)
+        self.timeout = config.get('slack_timeout', 10)
+        
+        if self.webhook_url and not self.webhook_url.startswith(self.WEBHOOK_PREFIX):
+            print(f"Warning: slack_webhook_url must start with {self.WEBHOOK_PREFIX}, Slack disabled")
+            self.webhook_url = ''
+    
+    def is_configured(self) -> bool:
+        """Check if Slack is configured."""
+        return bool(self.webhook_url)
+    
+    def send(self, title: str, message: str, **kwargs) -> bool:
+        """
+        Send notification to Slack as a header, a section and a link button.
+        
+        Args:
+            title: Notification title
+            message: Notification message
+            **kwargs: Optional parameters:
+                - url: URL opened by the "查看帖子" (view post) button
+                
+        Returns:
+            True if sent successfully
+        """
+        if not self.is_configured():
+            print("Slack not configured, skipping notification")
+            return False
+        
+        blocks = [
+            # Header blocks are plain text and limited to 150 characters
+            {'type': 'header', 'text': {'type': 'plain_text', 'text': title[:150], 'emoji': True}},
+            {'type': 'section', 'text': {'type': 'mrkdwn', 'text': message[:3000]}}
+        ]
+        if kwargs.get('url'):
+            blocks.append({'type': 'actions', 'elements': [{
+                'type': 'button',
+                'text': {'type': 'plain_text', 'text': translate('view_post', self.locale)},
+                'url': kwargs['url']
+            }]})
+        
+        # text is the fallback shown in notifications and clients without Block Kit
+        payload = {'text': f"{title}\n{message}", 'blocks': blocks}
+        if self.username:
+            payload['username'] = self.username
+        
+        try:
+            response = requests.post(self.webhook_url, json=payload, timeout=self.timeout)
+            response.raise_for_status()
+            return True
+        except requests.exceptions.RequestException as e:
+            print(f"Failed to send Slack notification: {e}")
+            return False
+        except Exception as e:
+            print(f"Error sending Slack notification: {e}")
+            return False
+
+
 class ConsoleNotificationSender(NotificationSender):
     """Console notification sender for testing/debugging."""
     
+    name = 'console'
+    
     def __init__(self, config: Dict[str, Any] = None):
         """Initialize console sender."""
         self.enabled = config.get('console_notification_enabled', True) if config else True
+        # Write to this file instead of stdout, e.g. when stdout is discarded by a daemon
+        self.log_file = config.get('console_log_file') if config else None
+        # Set by capturing(): notifications are kept here instead of being written
+        self.captured: Optional[List[str]] = None
+        self._write_lock = threading.Lock()
+    
+    @classmethod
+    def capturing(cls, config: Dict[str, Any] = None) -> 'ConsoleNotificationSender':
+        """Console sender that keeps notifications for captured_output, for tests."""
+        sender = cls(config)
+        sender.captured = []
+        return sender
+    
+    def captured_output(self) -> List[str]:
+        """Notifications sent by a capturing() sender, oldest first, as they would be printed."""
+        with self._write_lock:
+            return list(self.captured or [])
     
     def is_configured(self) -> bool:
         """Console sender is always configured."""
@@ -132,12 +427,12 @@ class ConsoleNotificationSender(NotificationSender):
     
     def send(self, title: str, message: str, **kwargs) -> bool:
         """
-        Print notification to console.
+        Print notification to console, or append it to console_log_file if set.
         
         Args:
             title: Notification title
             message: Notification message
-            **kwargs: Ignored for console sender
+            **kwargs: url and check_id are printed, the rest is ignored
             
         Returns:
             Always True
@@ -145,15 +440,38 @@ class ConsoleNotificationSender(NotificationSender):
         if not self.enabled:
             return False
         
-        print(f"\n{'='*80}")
-        print(f"📱 NOTIFICATION")
-        print(f"{'='*80}")
-        print(f"Title: {title}")
-        print(f"Message: {message}")
+        lines = [
+            f"\n{'='*80}",
+            f"📱 NOTIFICATION",
+            f"{'='*80}",
+            f"Title: {title}",
+            f"Message: {message}"
+        ]
         if kwargs.get('url'):
-            print(f"URL: {kwargs['url']}")
-        print(f"{'='*80}\n")
-        return True
+            lines.append(f"URL: {kwargs['url']}")
+        if kwargs.get('check_id'):
+            lines.append(f"Check ID: {kwargs['check_id']}")
+        lines.append(f"{'='*80}\n")
+        output = '\n'.join(lines) + '\n'
+        
+        if self.captured is not None:
+            with self._write_lock:
+                self.captured.append(output)
+            return True
+        if not self.log_file:
+            print(output, end='')
+            return True
+        
+        # One write per notification under a lock so concurrent sends don't interleave
+        try:
+            with self._write_lock:
+                with open(self.log_file, 'a', encoding='utf-8') as f:
+                    f.write(output)
+                    f.flush()
+            return True
+        except OSError as e:
+            print(f"Failed to write notification to {self.log_file}: {e}")
+            return False
 
 
 class NotificationManager:
@@ -168,38 +486,99 @@ class NotificationManager:
         """
         self.senders: List[NotificationSender] = []
         
+        # Maximum message length per sender name, e.g. {"bark": 1000}
+        self.max_message_length: Dict[str, int] = config.get('max_message_length') or {}
+        
+        # Sends beyond max_concurrent_notifications wait for a running one to finish
+        self.send_semaphore = _send_semaphore(config.get('max_concurrent_notifications'))
+        
         # Initialize Bark sender if configured
         if config.get('bark_enabled', False):
-            bark_sender = BarkNotificationSender(config)
-            if bark_sender.is_configured():
-                self.senders.append(bark_sender)
+            self._add_sender(BarkNotificationSender(config), config)
+        
+        # Initialize Pushbullet sender if configured
+        if config.get('pushbullet_enabled', False):
+            self._add_sender(PushbulletNotificationSender(config), config)
+        
+        # Initialize Slack sender if configured
+        if config.get('slack_enabled', False):
+            self._add_sender(SlackNotificationSender(config), config)
         
         # Always add console sender for debugging (can be disabled in config)
-        console_sender = ConsoleNotificationSender(config)
-        if console_sender.is_configured():
-            self.senders.append(console_sender)
+        self._add_sender(ConsoleNotificationSender(config), config)
     
-    def send(self, title: str, message: str, **kwargs) -> int:
+    def _add_sender(self, sender: NotificationSender, config: Dict[str, Any]):
+        """Add a sender if it is configured, applying its <name>_schedule and <name>_supports_markdown."""
+        if not sender.is_configured():
+            return
+        sender.enabled_schedule = config.get(f'{sender.name}_schedule')
+        sender.schedule_timezone = config.get('timezone')
+        sender.supports_markdown = config.get(f'{sender.name}_supports_markdown', sender.supports_markdown)
+        self.senders.append(sender)
+    
+    def send(self, title: str, message: str, disable_notifiers: Collection[str] = (), **kwargs) -> int:
         """
         Send notification via all configured senders.
+        Senders outside their enabled_schedule are skipped and not counted.
+        Messages are truncated to the sender's max_message_length.
+        Blocks while max_concurrent_notifications sends are in progress.
         
         Args:
             title: Notification title
             message: Notification message
-            **kwargs: Additional parameters passed to senders
+            disable_notifiers: Sender names to skip, e.g. a thread's ["bark"]
+            **kwargs: Additional parameters passed to senders, e.g. check_id of the
+                check that found the post, plus:
+                - markdown_message: Markdown variant of message for senders with supports_markdown
             
         Returns:
             Number of successful sends
         """
+        markdown_message = kwargs.pop('markdown_message', None)
         success_count = 0
         for sender in self.senders:
-            if sender.send(title, message, **kwargs):
+            if sender.name in disable_notifiers or not sender.is_active():
+                continue
+            sender_message = markdown_message if markdown_message and sender.supports_markdown else message
+            limit = self.max_message_length.get(sender.name)
+            if limit:
+                sender_message = truncate_text(sender_message, limit)
+            with self.send_semaphore or nullcontext():
+                ok = sender.send(title, sender_message, **kwargs)
+            if ok:
                 success_count += 1
+                sender.consecutive_failures = 0
+                sender.last_success_at = datetime.now()
+            else:
+                sender.consecutive_failures += 1
+                sender.last_failure_at = datetime.now()
         return success_count
     
     def has_senders(self) -> bool:
         """Check if any senders are configured."""
         return len(self.senders) > 0
+    
+    def sender_status(self) -> List[Dict[str, Any]]:
+        """
+        Status of every built-in sender, configured or not.
+        
+        Returns:
+            One entry per name in SENDER_NAMES with enabled, active (inside its
+            schedule now), consecutive_failures, last_success_at and last_failure_at
+        """
+        senders = {sender.name: sender for sender in self.senders}
+        status = []
+        for name in SENDER_NAMES:
+            sender = senders.get(name)
+            status.append({
+                'name': name,
+                'enabled': sender is not None,
+                'active': sender.is_active() if sender else False,
+                'consecutive_failures': sender.consecutive_failures if sender else 0,
+                'last_success_at': sender.last_success_at.isoformat() if sender and sender.last_success_at else None,
+                'last_failure_at': sender.last_failure_at.isoformat() if sender and sender.last_failure_at else None
+            })
+        return status
 
 
 if __name__ == '__main__':
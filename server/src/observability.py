@@ -0,0 +1,83 @@
+#!/usr/bin/env python3
+"""
+Optional error reporting for NGA monitor.
+Reports check failures and uncaught exceptions to Sentry when sentry_dsn is configured.
+"""
+
+import threading
+import traceback
+from typing import Dict, Any
+
+from . import __version__
+
+try:
+    import sentry_sdk
+except ImportError:
+    sentry_sdk = None
+
+RELEASE = f"nga-reminder@{__version__}"
+
+_sentry_enabled = False
+
+
+def init_sentry(config: Dict[str, Any]) -> bool:
+    """
+    Initialize the Sentry SDK if a DSN is configured.
+    Safe to call more than once; only the first successful call initializes the SDK.
+
+    Args:
+        config: Configuration dictionary
+
+    Returns:
+        True if Sentry reporting is active
+    """
+    global _sentry_enabled
+
+    if _sentry_enabled:
+        return True
+
+    dsn = config.get('sentry_dsn')
+    if not dsn:
+        return False
+
+    if sentry_sdk is None:
+        print("Warning: sentry_dsn is set but sentry-sdk is not installed. Run: pip install sentry-sdk")
+        return False
+
+    sentry_sdk.init(
+        dsn=dsn,
+        release=RELEASE,
+        environment=config.get('sentry_environment', 'production')
+    )
+    _sentry_enabled = True
+    print("✓ Sentry error reporting enabled")
+    return True
+
+
+def capture_exception(error: BaseException):
+    """Report an exception to Sentry if reporting is enabled."""
+    if _sentry_enabled:
+        sentry_sdk.capture_exception(error)
+
+
+def install_thread_excepthook():
+    """
+    Log uncaught exceptions in background threads with their traceback and report them to Sentry.
+    Without it a crashed thread only leaves Python's default stderr dump behind.
+    """
+    def excepthook(args):
+        if args.exc_type is SystemExit:
+            return
+        name = args.thread.name if args.thread else 'unknown'
+        print(f"Error: Uncaught exception in thread {name}: {args.exc_value}")
+        traceback.print_exception(args.exc_type, args.exc_value, args.exc_traceback)
+        if args.exc_value is not None:
+            capture_exception(args.exc_value)
+
+    threading.excepthook = excepthook
+
+
+def capture_message(message: str, level: str = 'error'):
+    """Report a message to Sentry if reporting is enabled."""
+    if _sentry_enabled:
+        sentry_sdk.capture_message(message, level=level)
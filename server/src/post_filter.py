@@ -0,0 +1,217 @@
+#!/usr/bin/env python3
+"""
+Per-thread post filters for notifications.
+
+Configured as an optional "filter" object on a monitored thread:
+
+    "filter": {
+        "include_keywords": ["更新", "公告"],
+        "exclude_keywords": ["广告"],
+        "regex": "版本 ?[0-9.]+",
+        "author_uids": [150058],
+        "author_names": ["某用户"],
+        "case_sensitive": false,
+        "redact_spoilers": true
+    }
+
+Every criterion that is set must match; empty lists and a missing regex
+match any post.
+
+Which posts are notified about in the first place is decided by the thread's
+author_notification UIDs and its "notification_rules"; a post qualifies if
+any of them matches:
+
+    "notification_rules": [
+        {"type": "author", "uid": 150058},
+        {"type": "keywords", "keywords": ["开奖"]},
+        {"type": "any"}
+    ]
+"""
+
+import re
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional, Set
+
+from .bbcode import strip_bbcode
+
+
+@dataclass
+class PostFilter:
+    """Criteria a post must meet to trigger a notification."""
+    include_keywords: List[str] = field(default_factory=list)
+    exclude_keywords: List[str] = field(default_factory=list)
+    regex: Optional[str] = None
+    author_uids: List[int] = field(default_factory=list)
+    author_names: List[str] = field(default_factory=list)
+    case_sensitive: bool = False
+    redact_spoilers: bool = False
+
+    @classmethod
+    def from_config(cls, options: Optional[Dict[str, Any]]) -> Optional['PostFilter']:
+        """
+        Build a filter from a thread's "filter" config object.
+
+        Returns:
+            PostFilter, or None if the thread has no filter
+        """
+        if not options:
+            return None
+        return cls(
+            include_keywords=list(options.get('include_keywords') or []),
+            exclude_keywords=list(options.get('exclude_keywords') or []),
+            regex=options.get('regex'),
+            author_uids=[int(uid) for uid in options.get('author_uids') or []],
+            author_names=list(options.get('author_names') or []),
+            case_sensitive=bool(options.get('case_sensitive', False)),
+            redact_spoilers=bool(options.get('redact_spoilers', False)),
+        )
+
+    def matches(self, post: Dict[str, Any]) -> bool:
+        """
+        Check whether a post passes the filter.
+
+        Keywords and the regex are matched against the BBCode-stripped content.
+
+        Args:
+            post: Post dictionary from parse_page_result
+
+        Returns:
+            True if the post meets every configured criterion
+        """
+        if self.author_uids and post.get('author_uid') not in self.author_uids:
+            return False
+        if self.author_names and not any(self._equal(post.get('author_name') or '', name)
+                                         for name in self.author_names):
+            return False
+
+        content = strip_bbcode(post.get('content') or '')
+        if self.include_keywords and not any(self._contains(content, keyword)
+                                             for keyword in self.include_keywords):
+            return False
+        if any(self._contains(content, keyword) for keyword in self.exclude_keywords):
+            return False
+        if self.regex is not None:
+            flags = 0 if self.case_sensitive else re.IGNORECASE
+            if not re.search(self.regex, content, flags):
+                return False
+        return True
+
+    def _contains(self, text: str, keyword: str) -> bool:
+        if self.case_sensitive:
+            return keyword in text
+        return keyword.casefold() in text.casefold()
+
+    def _equal(self, a: str, b: str) -> bool:
+        if self.case_sensitive:
+            return a == b
+        return a.casefold() == b.casefold()
+
+
+def rule_matches(rule: Dict[str, Any], post: Dict[str, Any]) -> bool:
+    """
+    Check whether a post matches one of a thread's notification_rules.
+
+    "author" matches posts by rule["uid"], "keywords" posts containing one of
+    rule["keywords"] (case-insensitive, BBCode stripped), "any" every post.
+    """
+    kind = rule.get('type')
+    if kind == 'author':
+        return post.get('author_uid') == rule.get('uid')
+    if kind == 'keywords':
+        keywords = list(rule.get('keywords') or [])
+        return bool(keywords) and PostFilter(include_keywords=keywords).matches(post)
+    return kind == 'any'
+
+
+def thread_matches_post(post: Dict[str, Any], notification_uids: Set[int],
+                        post_filter: Optional[PostFilter],
+                        excluded_uids: Set[int] = frozenset(),
+                        rules: List[Dict[str, Any]] = ()) -> bool:
+    """
+    Check whether a new post in a monitored thread triggers a notification.
+
+    Args:
+        post: Post dictionary from parse_page_result
+        notification_uids: The thread's author_notification UIDs (empty for none)
+        post_filter: The thread's filter, or None
+        excluded_uids: The thread's author_exclusion UIDs, never notified
+        rules: The thread's notification_rules
+
+    Returns:
+        True if the author is not excluded, the author is in notification_uids
+        or any rule matches, and the post passes the filter
+    """
+    if post.get('author_uid') in excluded_uids:
+        return False
+    if post.get('author_uid') not in notification_uids and not any(rule_matches(rule, post) for rule in rules):
+        return False
+    return post_filter is None or post_filter.matches(post)
+
+
+def validate_notification_rules(rules: Any, prefix: str) -> List[str]:
+    """
+    Validate a thread's "notification_rules" array from the configuration.
+
+    Args:
+        rules: Parsed notification_rules value
+        prefix: Config path used in error messages
+
+    Returns:
+        List of validation error messages (empty if valid)
+    """
+    if not isinstance(rules, list):
+        return [f'{prefix}: must be an array of rules']
+
+    errors = []
+    for i, rule in enumerate(rules):
+        if not isinstance(rule, dict):
+            errors.append(f'{prefix}[{i}]: must be an object')
+        elif rule.get('type') == 'author':
+            if not isinstance(rule.get('uid'), int) or isinstance(rule.get('uid'), bool):
+                errors.append(f'{prefix}[{i}].uid: must be a UID')
+        elif rule.get('type') == 'keywords':
+            keywords = rule.get('keywords')
+            if not isinstance(keywords, list) or not keywords or not all(isinstance(k, str) for k in keywords):
+                errors.append(f'{prefix}[{i}].keywords: must be a non-empty array of strings')
+        elif rule.get('type') != 'any':
+            errors.append(f'{prefix}[{i}].type: must be "author", "keywords" or "any"')
+    return errors
+
+
+def validate_post_filter(options: Any, prefix: str) -> List[str]:
+    """
+    Validate a thread's "filter" object from the configuration.
+
+    Args:
+        options: Parsed filter value
+        prefix: Config path used in error messages
+
+    Returns:
+        List of validation error messages (empty if valid)
+    """
+    if not isinstance(options, dict):
+        return [f'{prefix}: must be an object']
+
+    errors = []
+    for key in ('include_keywords', 'exclude_keywords', 'author_names'):
+        value = options.get(key)
+        if value is not None and (not isinstance(value, list)
+                                  or not all(isinstance(item, str) for item in value)):
+            errors.append(f'{prefix}.{key}: must be an array of strings')
+
+    uids = options.get('author_uids')
+    if uids is not None and (not isinstance(uids, list) or not all(
+            isinstance(uid, int) and not isinstance(uid, bool) for uid in uids)):
+        errors.append(f'{prefix}.author_uids: must be an array of UIDs')
+
+    regex = options.get('regex')
+    if regex is not None:
+        try:
+            re.compile(regex)
+        except (re.error, TypeError) as e:
+            errors.append(f'{prefix}.regex: invalid regular expression ({e})')
+
+    for key in ('case_sensitive', 'redact_spoilers'):
+        if key in options and not isinstance(options[key], bool):
+            errors.append(f'{prefix}.{key}: must be true or false')
+    return errors
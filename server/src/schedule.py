@@ -0,0 +1,155 @@
+#!/usr/bin/env python3
+"""
+Weekly time-window schedules for NGA monitor.
+
+A schedule is a list of windows; it is active when any window matches:
+
+    [{"days": "mon-fri", "start": "09:00", "end": "18:00"},
+     {"days": ["sat", "sun"], "start": "10:00", "end": "12:00"}]
+
+days is optional (default: every day) and also accepts "weekdays" and
+"weekends". start and end default to 00:00, and a window with equal start
+and end covers the whole day. A window whose end is before its start wraps
+past midnight, e.g. 22:00-07:00. A window may set "timezone" (an IANA name
+such as "America/New_York") to be evaluated in that zone instead of the
+default timezone or the system local time.
+"""
+
+from datetime import datetime, time as dtime
+from typing import Any, Dict, List, Optional, Set, Union
+from zoneinfo import ZoneInfo, ZoneInfoNotFoundError
+
+DAY_NAMES = ['mon', 'tue', 'wed', 'thu', 'fri', 'sat', 'sun']
+
+DAY_ALIASES = {'weekdays': {0, 1, 2, 3, 4}, 'weekends': {5, 6}}
+
+
+def parse_time(value: str) -> dtime:
+    """
+    Parse a HH:MM time of day.
+
+    Raises:
+        ValueError: If the value is not a valid HH:MM time
+    """
+    hour, minute = value.split(':')
+    return dtime(int(hour), int(minute))
+
+
+def expand_days(days: Union[str, List[str], None]) -> Set[int]:
+    """
+    Expand a day specification into weekday numbers (Monday is 0).
+
+    Args:
+        days: None, "daily" or "*" for every day, a list of day names, or a
+            comma-separated string of day names and ranges like "mon-fri,sun".
+            "weekdays" and "weekends" may be used in place of day names.
+
+    Returns:
+        Set of weekday numbers
+
+    Raises:
+        ValueError: If a day name is unknown
+    """
+    if days is None or days in ('daily', '*'):
+        return set(range(7))
+
+    parts = days if isinstance(days, list) else days.split(',')
+    result = set()
+    for part in parts:
+        part = part.strip().lower()
+        if part in DAY_ALIASES:
+            result |= DAY_ALIASES[part]
+        elif '-' in part:
+            first, last = (DAY_NAMES.index(d.strip()) for d in part.split('-', 1))
+            day = first
+            result.add(day)
+            while day != last:
+                day = (day + 1) % 7
+                result.add(day)
+        else:
+            result.add(DAY_NAMES.index(part))
+    return result
+
+
+def is_time_range(start: dtime, end: dtime, now: dtime) -> bool:
+    """
+    Check whether a time of day falls within [start, end).
+    Ranges with end before start wrap past midnight; equal start and end
+    cover the whole day.
+    """
+    if start == end:
+        return True
+    if start < end:
+        return start <= now < end
+    return now >= start or now < end
+
+
+def is_active(schedule: Optional[List[Dict[str, Any]]], now: Optional[datetime] = None,
+              default_timezone: Optional[str] = None) -> bool:
+    """
+    Check whether a schedule is active.
+
+    Args:
+        schedule: List of windows, or None/empty for always active
+        now: Time to check (default: current time); naive times are system local time
+        default_timezone: Timezone of windows without their own "timezone"
+            (default: system local time)
+
+    Returns:
+        True if any window matches
+    """
+    if not schedule:
+        return True
+
+    now = now or datetime.now()
+    for window in schedule:
+        timezone = window.get('timezone') or default_timezone
+        local = now.astimezone(ZoneInfo(timezone)) if timezone else now
+        if local.weekday() not in expand_days(window.get('days')):
+            continue
+        if is_time_range(parse_time(window.get('start', '00:00')),
+                         parse_time(window.get('end', '00:00')), local.time()):
+            return True
+    return False
+
+
+def validate_timezone(value: Any, prefix: str) -> List[str]:
+    """Validate an IANA timezone name such as "Asia/Shanghai"."""
+    try:
+        ZoneInfo(value)
+    except (ZoneInfoNotFoundError, ValueError, TypeError):
+        return [f'{prefix}: unknown timezone, use an IANA name like "Asia/Shanghai"']
+    return []
+
+
+def validate_schedule(schedule: Any, prefix: str) -> List[str]:
+    """
+    Validate a schedule from the configuration.
+
+    Args:
+        schedule: Parsed schedule value
+        prefix: Config path used in error messages
+
+    Returns:
+        List of validation error messages (empty if valid)
+    """
+    if not isinstance(schedule, list):
+        return [f'{prefix}: must be an array of time windows']
+
+    errors = []
+    for i, window in enumerate(schedule):
+        if not isinstance(window, dict):
+            errors.append(f'{prefix}[{i}]: must be an object')
+            continue
+        for field in ('start', 'end'):
+            try:
+                parse_time(window.get(field, '00:00'))
+            except (ValueError, AttributeError, TypeError):
+                errors.append(f'{prefix}[{i}].{field}: must be a HH:MM time')
+        try:
+            expand_days(window.get('days'))
+        except (ValueError, AttributeError, TypeError):
+            errors.append(f'{prefix}[{i}].days: unknown day, use {", ".join(DAY_NAMES)}')
+        if window.get('timezone') is not None:
+            errors.extend(validate_timezone(window['timezone'], f'{prefix}[{i}].timezone'))
+    return errors
@@ -0,0 +1,163 @@
+#!/usr/bin/env python3
+"""
+Formatting helpers shared by the monitor and notification code.
+"""
+
+import re
+import time
+from datetime import datetime
+from typing import Any, Dict
+
+from .bbcode import strip_bbcode
+from .i18n import DEFAULT_LOCALE, translate
+
+# A sentence ends at 。！？ or at . ! ? followed by whitespace, so "3.5" and URLs stay whole
+_SENTENCE_RE = re.compile(r'.+?(?:[。！？]+|[.!?]+(?=\s|$)|$)', re.DOTALL)
+
+
+def format_relative_time(timestamp: int, locale: str = DEFAULT_LOCALE) -> str:
+    """
+    Format a Unix timestamp relative to now.
+
+    Args:
+        timestamp: Unix timestamp in seconds (0 if unknown)
+        locale: Locale of the returned text
+
+    Returns:
+        "just now", "N minutes ago", "N hours ago", "N days ago" (localized), or
+        the full local date for posts older than a week. Empty string if timestamp is 0.
+    """
+    if not timestamp or timestamp <= 0:
+        return ''
+
+    seconds = max(0, int(time.time() - timestamp))
+    if seconds < 60:
+        return translate('just_now', locale)
+    for unit, unit_seconds in (('day', 86400), ('hour', 3600), ('minute', 60)):
+        if unit == 'day' and seconds >= 7 * 86400:
+            break
+        if seconds >= unit_seconds:
+            n = seconds // unit_seconds
+            return translate(f'{unit}_ago' if n == 1 else f'{unit}s_ago', locale, n=n)
+    return datetime.fromtimestamp(timestamp).strftime('%Y-%m-%d %H:%M')
+
+
+def thread_url(base_url: str, tid: int) -> str:
+    """
+    Link to a thread on the NGA website.
+
+    Args:
+        base_url: Scheme and host of the site, e.g. "https://bbs.nga.cn"
+        tid: Thread ID
+    """
+    return f"{base_url.rstrip('/')}/read.php?tid={tid}"
+
+
+def post_url(base_url: str, post: Dict[str, Any]) -> str:
+    """
+    Link to a post on the NGA website.
+
+    Args:
+        base_url: Scheme and host of the site, e.g. "https://bbs.nga.cn"
+        post: Post dictionary with tid and pid
+    """
+    return f"{thread_url(base_url, post['tid'])}&pid={post['pid']}"
+
+
+def truncate_text(text: str, max_chars: int, ellipsis: str = '…') -> str:
+    """
+    Truncate text to at most max_chars characters, preferring a word boundary.
+
+    Args:
+        text: Text to truncate
+        max_chars: Maximum length of the result, including the ellipsis
+        ellipsis: Appended when the text is cut
+
+    Returns:
+        The text unchanged if short enough, otherwise cut at the last space in the
+        allowed range (or mid-word when there is none, e.g. for Chinese text)
+    """
+    if len(text) <= max_chars:
+        return text
+    if max_chars <= len(ellipsis):
+        return text[:max_chars]
+
+    cut = text[:max_chars - len(ellipsis)]
+    space = cut.rfind(' ')
+    # Only back up to a space if that keeps most of the allowed length
+    if space > len(cut) // 2:
+        cut = cut[:space]
+    return cut.rstrip() + ellipsis
+
+
+def content_preview(content: str, max_chars: int = 100) -> str:
+    """
+    Plain-text preview of raw post content.
+
+    Args:
+        content: Raw post content (BBCode and HTML entities)
+        max_chars: Maximum length of the preview
+
+    Returns:
+        The content with BBCode stripped, cut at a word boundary like truncate_text
+    """
+    return truncate_text(strip_bbcode(content or ''), max_chars)
+
+
+def close_code_fences(text: str) -> str:
+    """
+    Close a Markdown ``` fence left open when truncation cut a code block.
+
+    Args:
+        text: Truncated Markdown text
+
+    Returns:
+        The text with a partial fence at the cut removed and a closing fence
+        appended if an odd number of fences remain
+    """
+    # A cut inside or right after a fence leaves up to three backticks before the ellipsis
+    text = re.sub(r'\n`{1,3}…$', '…', text)
+    if text.count('```') % 2:
+        text += '\n```'
+    return text
+
+
+def truncate_lines(text: str, max_lines: int, ellipsis: str = '…') -> str:
+    """
+    Keep the first max_lines non-empty lines of text.
+
+    Args:
+        text: Text to truncate
+        max_lines: Maximum number of non-empty lines kept
+        ellipsis: Appended as its own line when lines are dropped
+
+    Returns:
+        The non-empty lines joined with newlines, plus the ellipsis if cut
+    """
+    lines = [line for line in text.split('\n') if line.strip()]
+    if len(lines) <= max_lines:
+        return '\n'.join(lines)
+    return '\n'.join(lines[:max_lines] + [ellipsis])
+
+
+def truncate_sentences(text: str, max_sentences: int, ellipsis: str = '…') -> str:
+    """
+    Keep the first max_sentences sentences of text.
+
+    Args:
+        text: Text to truncate
+        max_sentences: Maximum number of sentences kept
+        ellipsis: Appended when sentences are dropped
+
+    Returns:
+        The text up to the end of the last kept sentence, plus the ellipsis if cut
+    """
+    count = 0
+    for match in _SENTENCE_RE.finditer(text):
+        if not match.group().strip():
+            continue
+        count += 1
+        if count == max_sentences:
+            rest = text[match.end():]
+            return text[:match.end()].strip() + (ellipsis if rest.strip() else '')
+    return text.strip()
@@ -0,0 +1,118 @@
+#!/usr/bin/env python3
+"""
+Shared helpers for the test scripts.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import json
+import tempfile
+import threading
+from typing import Any, Dict, List, Optional, Tuple
+
+from src.config_migrations import CURRENT_SCHEMA_VERSION
+from src.notification import NotificationSender
+
+FIXTURE_TID = 12345
+
+
+def config_fixture(**overrides) -> Dict[str, Any]:
+    """
+    Minimal valid configuration for tests.
+    
+    One monitored thread (FIXTURE_TID, notifying about author 100), console
+    notifications enabled, and no rate-limit delay so tests run fast.
+    
+    Args:
+        **overrides: Top-level keys to set or replace
+    """
+    config = {
+        'schema_version': CURRENT_SCHEMA_VERSION,
+        'ngaPassportUid': 'test_uid',
+        'ngaPassportCid': 'test_cid',
+        'max_threads': 1,
+        'rate_limit_per_minute': 60000,
+        'console_notification_enabled': True,
+        'error_alert_threshold': 0,
+        'monitored_threads': [
+            {'tid': FIXTURE_TID, 'author_notification': [100], 'check_interval': 60, 'enabled': True}
+        ]
+    }
+    config.update(overrides)
+    return config
+
+
+def write_config(config: Optional[Dict[str, Any]] = None, directory: Optional[str] = None) -> str:
+    """
+    Write a config (default: config_fixture()) to config.json in a temporary directory.
+    
+    Returns:
+        Path of the written file
+    """
+    directory = directory or tempfile.mkdtemp()
+    path = os.path.join(directory, 'config.json')
+    with open(path, 'w', encoding='utf-8') as f:
+        json.dump(config if config is not None else config_fixture(), f, ensure_ascii=False)
+    return path
+
+
+class MockSender(NotificationSender):
+    """
+    Notification sender that records every call instead of sending.
+    
+    Add it to a NotificationManager to test the full send path:
+    
+        mock = MockSender()
+        monitor = ThreadMonitor(db_path, config_path, senders=[mock])
+        ...
+        assert mock.calls()[0][0] == '📬 Thread title'
+    """
+    
+    name = 'mock'
+    
+    def __init__(self, succeed: bool = True):
+        """
+        Args:
+            succeed: Value returned by send, to simulate failing senders
+        """
+        self.succeed = succeed
+        self._calls: List[Tuple[str, str, Dict[str, Any]]] = []
+        self._lock = threading.Lock()
+    
+    def is_configured(self) -> bool:
+        return True
+    
+    def send(self, title: str, message: str, **kwargs) -> bool:
+        with self._lock:
+            self._calls.append((title, message, dict(kwargs)))
+        return self.succeed
+    
+    def calls(self) -> List[Tuple[str, str, Dict[str, Any]]]:
+        """Recorded (title, message, kwargs) tuples, oldest first."""
+        with self._lock:
+            return list(self._calls)
+    
+    def assert_notification_sent_for_pid(self, pid: int):
+        """Assert that a notification linked to post pid, i.e. its url ends with &pid=<pid>."""
+        assert any((kwargs.get('url') or '').endswith(f'&pid={pid}') for _, _, kwargs in self.calls()), \
+            f'No notification for pid {pid}; calls:\n{self._format_calls()}'
+    
+    def assert_title_contains(self, text: str):
+        """Assert that some notification title contains text."""
+        assert any(text in title for title, _, _ in self.calls()), \
+            f'No notification title contains {text!r}; calls:\n{self._format_calls()}'
+    
+    def assert_message_contains(self, text: str):
+        """Assert that some notification message contains text."""
+        assert any(text in message for _, message, _ in self.calls()), \
+            f'No notification message contains {text!r}; calls:\n{self._format_calls()}'
+    
+    def assert_url_contains(self, text: str):
+        """Assert that some notification url contains text."""
+        assert any(text in (kwargs.get('url') or '') for _, _, kwargs in self.calls()), \
+            f'No notification url contains {text!r}; calls:\n{self._format_calls()}'
+    
+    def _format_calls(self) -> str:
+        return '\n'.join(f'  {i}: title={title!r} message={message!r} kwargs={kwargs!r}'
+                         for i, (title, message, kwargs) in enumerate(self.calls())) or '  (none)'
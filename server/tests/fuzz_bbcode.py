@@ -0,0 +1,99 @@
+#!/usr/bin/env python3
+"""
+Fuzz harness for BBCode processing.
+
+Feeds random inputs to strip_bbcode, format_code_blocks and redact_spoilers:
+arbitrary bytes decoded as UTF-8, soups of NGA tags and fragments, and
+deeply nested or unclosed tags. Any exception, or a single call slower than
+--max-ms (a sign of catastrophic regex backtracking), is reported with the
+input that caused it.
+
+Usage:
+    python tests/fuzz_bbcode.py [--iterations 20000] [--seed N] [--max-ms 500]
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import argparse
+import random
+import time
+import traceback
+
+from src.bbcode import format_code_blocks, redact_spoilers, strip_bbcode
+
+FRAGMENTS = ['[b]', '[/b]', '[i]', '[/i]', '[quote]', '[/quote]', '[img]', '[/img]', '[url=', '[url]',
+             '[/url]', ']', '[', '[s]', '[/s]', '[s:ac:哭笑]', '[code]', '[/code]', '[code=js]', '```',
+             '[color=red]', '[/color]', '[size=120%]', '[pid=1,2,3]', '[uid=150058]', '<br/>', '<br>',
+             '&amp;', '&#', '&lt;', '\n', ' ', '\t', '中文', 'text', '=', '/', '\\', '\x00', '퟿']
+
+
+def random_bytes(rng):
+    data = bytes(rng.getrandbits(8) for _ in range(rng.randint(0, 256)))
+    return data.decode('utf-8', errors='ignore')
+
+
+def random_soup(rng):
+    return ''.join(rng.choice(FRAGMENTS) for _ in range(rng.randint(0, 200)))
+
+
+def random_nested(rng):
+    depth = rng.randint(1, 2000)
+    tag = rng.choice(['b', 'quote', 'code', 's', 'url=x', 'color=red'])
+    close = tag.split('=')[0]
+    body = f'[{tag}]' * depth + random_soup(rng)
+    # Sometimes leave the tags unclosed
+    if rng.random() < 0.5:
+        body += f'[/{close}]' * rng.randint(0, depth)
+    return body
+
+
+GENERATORS = [random_bytes, random_soup, random_nested]
+
+
+def run_case(content, max_ms):
+    """Return an error description, or None if every function handled the input."""
+    calls = [
+        ('strip_bbcode', lambda: strip_bbcode(content)),
+        ('format_code_blocks', lambda: strip_bbcode(format_code_blocks(content, 5, True))),
+        ('format_code_blocks (plain)', lambda: strip_bbcode(format_code_blocks(content, 5, False))),
+        ('redact_spoilers', lambda: strip_bbcode(redact_spoilers(content))),
+    ]
+    for name, call in calls:
+        start = time.perf_counter()
+        try:
+            result = call()
+        except Exception:
+            return f'{name} raised:\n{traceback.format_exc()}'
+        elapsed_ms = (time.perf_counter() - start) * 1000
+        if not isinstance(result, str):
+            return f'{name} returned {type(result).__name__}'
+        if elapsed_ms > max_ms:
+            return f'{name} took {elapsed_ms:.0f}ms on {len(content)} chars'
+    return None
+
+
+def main():
+    parser = argparse.ArgumentParser(description='Fuzz BBCode processing')
+    parser.add_argument('--iterations', type=int, default=20000)
+    parser.add_argument('--seed', type=int, default=None, help='Seed (default: random)')
+    parser.add_argument('--max-ms', type=float, default=500, help='Slowest acceptable call in ms')
+    args = parser.parse_args()
+    
+    seed = args.seed if args.seed is not None else random.randrange(2 ** 32)
+    rng = random.Random(seed)
+    print(f"Seed: {seed}")
+    
+    for i in range(args.iterations):
+        content = rng.choice(GENERATORS)(rng)
+        error = run_case(content, args.max_ms)
+        if error:
+            print(f"✗ Iteration {i}: {error}")
+            print(f"Input ({len(content)} chars): {content[:500]!r}")
+            sys.exit(1)
+    
+    print(f"✓ {args.iterations} inputs processed without errors")
+
+
+if __name__ == '__main__':
+    main()
@@ -0,0 +1,101 @@
+#!/usr/bin/env python3
+"""
+Test the Bark sender's request building.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from urllib.parse import parse_qs, urlsplit
+
+import requests
+
+from src import notification
+from src.notification import BarkNotificationSender
+
+
+def bark(**overrides):
+    config = {'bark_server_url': 'https://api.day.app', 'bark_device_key': 'abc123'}
+    config.update(overrides)
+    return BarkNotificationSender(config)
+
+
+def test_bark():
+    # Test 1: A trailing slash on server_url doesn't change the endpoint
+    assert bark().api_url() == 'https://api.day.app/abc123'
+    assert bark(bark_server_url='https://api.day.app/').api_url() == 'https://api.day.app/abc123'
+    assert bark(bark_server_url='https://example.com/bark/').api_url() == 'https://example.com/bark/abc123'
+    print("✓ Test 1 passed: server_url with and without trailing slash")
+    
+    # Test 2: Device keys that would break the URL path disable the sender
+    assert bark(bark_device_key='Ab-9_x').is_configured()
+    for key in ('abc/def', 'abc 123', 'key?x=1', 'ключ'):
+        sender = bark(bark_device_key=key)
+        assert sender.invalid_device_key and not sender.is_configured(), key
+        assert sender.send('title', 'message') is False
+    assert not bark(bark_device_key='').invalid_device_key
+    print("✓ Test 2 passed: Invalid device keys rejected")
+    
+    # Test 3: The post URL is percent-encoded in the query and arrives unchanged
+    sent = []
+    
+    class OkResponse:
+        def raise_for_status(self):
+            pass
+        
+        def json(self):
+            return {'code': 200}
+    
+    def fake_get(url, params=None, timeout=None):
+        sent.append(requests.Request('GET', url, params=params).prepare().url)
+        return OkResponse()
+    
+    post_url = 'https://bbs.nga.cn/read.php?tid=12345&pid=678#楼层 3&x'
+    original_get = notification.requests.get
+    notification.requests.get = fake_get
+    try:
+        assert bark().send('标题', '内容', url=post_url)
+    finally:
+        notification.requests.get = original_get
+    assert sent[0].isascii(), sent[0]
+    query = parse_qs(urlsplit(sent[0]).query)
+    assert query['url'] == [post_url], query
+    assert query['title'] == ['标题'] and query['body'] == ['内容'], query
+    print("✓ Test 3 passed: URL with CJK fragment encoded")
+    
+    # Test 4: repr doesn't show the device key
+    text = repr(bark(bark_device_key='secretkey42'))
+    assert 'secretkey42' not in text and "device_key='[redacted]'" in text, text
+    assert "server_url='https://api.day.app'" in text, text
+    print("✓ Test 4 passed: Device key redacted in repr")
+    
+    # Test 5: bark_api_version 2 posts JSON with the key in the body
+    posted = []
+    
+    def fake_post(url, json=None, timeout=None):
+        posted.append((url, json))
+        return OkResponse()
+    
+    original_post = notification.requests.post
+    notification.requests.post = fake_post
+    try:
+        assert bark(bark_api_version=2, bark_server_url='https://example.com/').send('标题', '内容')
+    finally:
+        notification.requests.post = original_post
+    url, body = posted[0]
+    assert url == 'https://example.com/push', url
+    assert body['device_key'] == 'abc123' and body['title'] == '标题' and body['body'] == '内容', body
+    print("✓ Test 5 passed: Bark v2 JSON request")
+    
+    # Test 6: bark_url_template replaces the endpoint and needs {server_url}
+    templated = bark(bark_url_template='{server_url}/api/{device_key}/{title}')
+    assert templated.api_url('标题 1', '内容') == 'https://api.day.app/api/abc123/%E6%A0%87%E9%A2%98%201'
+    assert not bark(bark_url_template='https://example.com/{device_key}').is_configured()
+    assert not bark(bark_url_template='{server_url}/{sound}').is_configured()
+    print("✓ Test 6 passed: Bark URL template")
+    
+    print("\n✓ All tests passed!")
+
+
+if __name__ == '__main__':
+    test_bark()
@@ -0,0 +1,43 @@
+#!/usr/bin/env python3
+"""
+Tests for configuration helpers.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.config import trim_inert_threads
+from tests.common import config_fixture
+
+
+def test_trim_inert_threads():
+    threads = [
+        {'tid': 1, 'author_notification': [100]},
+        {'tid': 2},
+        {'tid': 3, 'notification_rules': [{'type': 'keywords', 'keywords': ['release']}]},
+        {'tid': 4, 'author_notification': [], 'notification_rules': []},
+        {'tid': 5, 'notification_rules': [{'type': 'author', 'uid': 200}]},
+        {'tid': 6, 'filter': {'include_keywords': ['release'], 'author_names': ['Dev']}},
+        {'tid': 7, 'notification_rules': [{'type': 'any'}], 'enabled': False}
+    ]
+    config = config_fixture(monitored_threads=threads)
+    
+    # Test 1: Threads without author_notification UIDs or notification_rules are removed
+    removed = trim_inert_threads(config)
+    assert removed == [2, 4, 6], removed
+    print("✓ Test 1 passed: Inert threads removed and their TIDs returned")
+    
+    # Test 2: Threads with author UIDs, keyword, author or any rules are kept in order
+    assert [t['tid'] for t in config['monitored_threads']] == [1, 3, 5, 7], config['monitored_threads']
+    print("✓ Test 2 passed: Configured threads kept")
+    
+    # Test 3: Nothing left to trim
+    assert trim_inert_threads(config) == []
+    assert len(config['monitored_threads']) == 4
+    assert trim_inert_threads({}) == []
+    print("✓ Test 3 passed: No inert threads")
+
+
+if __name__ == '__main__':
+    test_trim_inert_threads()
+    print("\n✓ All tests passed!")
@@ -0,0 +1,250 @@
+#!/usr/bin/env python3
+"""
+Integration test for the check_thread -> notification pipeline.
+Runs the monitor against a local mock of the NGA app API.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import json
+import socket
+import tempfile
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from urllib.parse import parse_qs
+
+from src.monitor import ThreadMonitor
+from src.nga_crawler import NGACrawler
+from src.notification import ConsoleNotificationSender
+from tests.common import FIXTURE_TID, MockSender, config_fixture, write_config
+
+TID = FIXTURE_TID
+PER_PAGE = 20
+WATCHED_UID = 100
+
+
+class MockNGA:
+    """Canned NGA app API responses for one thread."""
+    
+    def __init__(self, total_posts):
+        self.total_posts = total_posts
+        self.requests = []
+    
+    def page(self, page_num):
+        total_pages = max(1, (self.total_posts + PER_PAGE - 1) // PER_PAGE)
+        start = (page_num - 1) * PER_PAGE
+        posts = [{
+            'pid': 1000 + n,
+            'tid': TID,
+            'fid': 1,
+            'author': {'uid': WATCHED_UID if n % 5 == 0 else 200, 'username': f'User{n}'},
+            'postdate': '2024-01-01 12:00',
+            'postdatetimestamp': 1704096000 + n,
+            'content': f'[b]Post[/b] {n}',
+            'lou': n
+        } for n in range(start, min(start + PER_PAGE, self.total_posts))]
+        return {
+            'code': 0, 'result': posts, 'tsubject': 'Mock Thread', 'tauthor': 'User0',
+            'tauthorid': WATCHED_UID, 'vrows': self.total_posts, 'totalPage': total_pages,
+            'currentPage': page_num, 'perPage': PER_PAGE
+        }
+
+
+def start_server(mock):
+    class Handler(BaseHTTPRequestHandler):
+        def do_POST(self):
+            length = int(self.headers.get('Content-Length', 0))
+            form = parse_qs(self.rfile.read(length).decode())
+            page_num = int(form['page'][0])
+            mock.requests.append(page_num)
+            body = json.dumps(mock.page(page_num)).encode()
+            self.send_response(200)
+            self.send_header('Content-Type', 'application/json')
+            self.send_header('Content-Length', str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+        
+        def log_message(self, *args):
+            pass
+    
+    server = ThreadingHTTPServer(('127.0.0.1', 0), Handler)
+    threading.Thread(target=server.serve_forever, daemon=True).start()
+    return server
+
+
+def test_check_thread_notifies():
+    mock = MockNGA(total_posts=45)
+    server = start_server(mock)
+    tmp = tempfile.mkdtemp()
+    config_path = write_config(config_fixture(
+        api_url=f'http://127.0.0.1:{server.server_port}/app_api.php'), tmp)
+    
+    try:
+        sender = MockSender()
+        monitor = ThreadMonitor(db_path=os.path.join(tmp, 'nga.db'), config_path=config_path,
+                                senders=[sender])
+        
+        # Test 1: Initial sync stores existing posts without notifying
+        monitor.load_from_config()
+        assert sender.calls() == [], sender.calls()
+        print("✓ Test 1 passed: Initial sync sends no notifications")
+        
+        # Test 2: New posts across two pages, only the watched author is notified
+        mock.total_posts = 62
+        mock.requests.clear()
+        result = monitor.check_thread(TID, verbose=False)
+        assert 'error' not in result, result
+        assert mock.requests == [1, 3, 4], mock.requests
+        assert result['total_new_posts'] == 17, result
+        expected = [n for n in range(45, 62) if n % 5 == 0]
+        assert result['notifications_sent'] == len(expected), result
+        calls = sender.calls()
+        # Links point at the host of the configured API endpoint
+        assert [kwargs['url'] for _, _, kwargs in calls] == [
+            f'http://127.0.0.1:{server.server_port}/read.php?tid={TID}&pid={1000 + n}' for n in expected]
+        assert calls[0][0] == '📬 Mock Thread'
+        assert calls[0][1].startswith('User45: Post 45')
+        sender.assert_notification_sent_for_pid(1050)
+        sender.assert_message_contains('User60: Post 60')
+        print("✓ Test 2 passed: New posts on later pages notified")
+        
+        # Test 3: Nothing new, nothing sent
+        result = monitor.check_thread(TID, verbose=False)
+        assert result['new_posts'] == 0 and len(sender.calls()) == len(calls), result
+        print("✓ Test 3 passed: No new posts")
+        
+        # Test 4: An unreachable endpoint fails over to the next one in api_urls
+        with socket.socket() as s:
+            s.bind(('127.0.0.1', 0))
+            dead_port = s.getsockname()[1]
+        urls = [f'http://127.0.0.1:{dead_port}/app_api.php',
+                f'http://127.0.0.1:{server.server_port}/app_api.php']
+        crawler = NGACrawler(write_config(config_fixture(api_urls=urls), tempfile.mkdtemp()))
+        assert crawler.fetch_page(TID, 1)['vrows'] == 62
+        assert crawler.base_url == urls[1], crawler.base_url
+        print("✓ Test 4 passed: Failover to the next endpoint")
+        
+        # Test 5: repr doesn't show the passport cookies
+        text = repr(crawler)
+        assert 'test_uid' not in text and 'test_cid' not in text, text
+        assert "ngaPassportCid='[redacted]'" in text, text
+        print("✓ Test 5 passed: Credentials redacted in repr")
+        
+        monitor.close()
+    finally:
+        server.shutdown()
+    
+    print("\n✓ All tests passed!")
+
+
+def test_single_page_thread():
+    mock = MockNGA(total_posts=8)
+    server = start_server(mock)
+    tmp = tempfile.mkdtemp()
+    config_path = write_config(config_fixture(
+        api_url=f'http://127.0.0.1:{server.server_port}/app_api.php'), tmp)
+    
+    try:
+        sender = MockSender()
+        console = ConsoleNotificationSender.capturing()
+        monitor = ThreadMonitor(db_path=os.path.join(tmp, 'nga.db'), config_path=config_path,
+                                senders=[sender, console])
+        monitor.load_from_config()
+        
+        # Test 1: New posts on page 1 don't fetch page 1 a second time
+        mock.total_posts = 12
+        mock.requests.clear()
+        result = monitor.check_thread(TID, verbose=False)
+        assert mock.requests == [1], mock.requests
+        assert result['total_new_posts'] == 4, result
+        assert result['notifications_sent'] == 1, result
+        sender.assert_title_contains('Mock Thread')
+        sender.assert_url_contains(f'tid={TID}')
+        output = console.captured_output()
+        assert len(output) == 1 and 'Title: 📬 Mock Thread' in output[0], output
+        print("✓ Test 1 passed: Page 1 fetched once")
+        
+        monitor.close()
+    finally:
+        server.shutdown()
+    
+    print("\n✓ All tests passed!")
+
+
+def test_preview_matches_check():
+    mock = MockNGA(total_posts=8)
+    server = start_server(mock)
+    tmp = tempfile.mkdtemp()
+    # Every post notifies through the rule, except those by the excluded author 200
+    thread = {'tid': TID, 'author_notification': None, 'notification_rules': [{'type': 'any'}],
+              'author_exclusion': [200], 'check_interval': 60, 'enabled': True}
+    config_path = write_config(config_fixture(
+        api_url=f'http://127.0.0.1:{server.server_port}/app_api.php', monitored_threads=[thread]), tmp)
+    
+    try:
+        sender = MockSender()
+        monitor = ThreadMonitor(db_path=os.path.join(tmp, 'nga.db'), config_path=config_path,
+                                senders=[sender])
+        monitor.load_from_config()
+        
+        # Test 1: Stored posts preview with the thread's rules and exclusions
+        assert monitor.preview_notification(TID, 5)['would_notify'] is True
+        assert monitor.preview_notification(TID, 3)['would_notify'] is False
+        print("✓ Test 1 passed: Preview of stored posts")
+        
+        # Test 2: The preview of posts not stored yet agrees with the check that finds them
+        mock.total_posts = 12
+        previews = {n: monitor.preview_notification(TID, n)['would_notify'] for n in range(8, 12)}
+        result = monitor.check_thread(TID, verbose=False)
+        assert result['notifications_sent'] == 1, result
+        sender.assert_notification_sent_for_pid(1010)
+        assert previews == {8: False, 9: False, 10: True, 11: False}, previews
+        print("✓ Test 2 passed: Preview and check agree")
+        
+        monitor.close()
+    finally:
+        server.shutdown()
+    
+    print("\n✓ All tests passed!")
+
+
+def test_markdown_notifications():
+    tmp = tempfile.mkdtemp()
+    thread = {'tid': TID, 'author_notification': [WATCHED_UID], 'max_content_chars': 40,
+              'check_interval': 60, 'enabled': True}
+    config_path = write_config(config_fixture(monitored_threads=[thread]), tmp)
+    sender = MockSender()
+    sender.supports_markdown = True
+    monitor = ThreadMonitor(db_path=os.path.join(tmp, 'nga.db'), config_path=config_path,
+                            senders=[sender])
+    code = '<br/>'.join(f'line_{n} = {n}' for n in range(20))
+    posts = [{'pid': 2000 + n, 'tid': TID, 'post_number': n, 'author_uid': WATCHED_UID,
+              'author_name': 'Coder', 'post_timestamp': 0,
+              'content': f'Fix {n}: [code]{code}[/code] done'} for n in range(2)]
+    
+    try:
+        # Test 1: The Markdown variant is cut to max_content_chars with its fence closed
+        monitor.flush_notifications([(TID, 'Code Thread', posts[0], 'check-1')])
+        message = sender.calls()[0][1]
+        assert message.startswith('Coder: Fix 0: \n```\nline_0 = 0'), message
+        assert len(message) < 60 and message.endswith('\n```'), message
+        print("✓ Test 1 passed: Markdown message truncated")
+        
+        # Test 2: A batch sends the summary, not the first post's Markdown message
+        monitor.batch_notifications = True
+        monitor.flush_notifications([(TID, 'Code Thread', post, 'check-2') for post in posts])
+        message = sender.calls()[1][1]
+        assert message.startswith('2') and 'Coder: Fix 1' in message and '```' not in message, message
+        print("✓ Test 2 passed: Batch summary sent to Markdown senders")
+    finally:
+        monitor.close()
+    
+    print("\n✓ All tests passed!")
+
+
+if __name__ == '__main__':
+    test_check_thread_notifies()
+    test_single_page_thread()
+    test_preview_matches_check()
+    test_markdown_notifications()
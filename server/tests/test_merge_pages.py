@@ -0,0 +1,49 @@
+#!/usr/bin/env python3
+"""
+Tests for merging fetched pages into a post list.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.database import find_post_by_pid, merge_pages
+
+
+def make_page(page_num, post_numbers):
+    return {
+        'currentPage': page_num,
+        'totalPage': 3,
+        'result': [
+            {'pid': 1000 + n, 'tid': 12345, 'lou': n, 'content': f'Post {n}',
+             'author': {'uid': 100, 'username': 'User1'}}
+            for n in post_numbers
+        ]
+    }
+
+
+def test_merge_pages():
+    # Test 1: Pages are merged in page order
+    posts = merge_pages([make_page(3, [40, 41]), make_page(1, [0, 1]), make_page(2, [20, 21])])
+    assert [p['post_number'] for p in posts] == [0, 1, 20, 21, 40, 41]
+    print("✓ Test 1 passed: Pages sorted by currentPage")
+    
+    # Test 2: A post that shifted onto the next page is kept once
+    posts = merge_pages([make_page(1, [18, 19]), make_page(2, [19, 20])])
+    assert [p['post_number'] for p in posts] == [18, 19, 20]
+    print("✓ Test 2 passed: Duplicate post numbers removed")
+    
+    # Test 3: No pages
+    assert merge_pages([]) == []
+    print("✓ Test 3 passed: Empty input")
+    
+    # Test 4: Posts are found by pid
+    posts = merge_pages([make_page(1, [0, 1]), make_page(2, [20])])
+    assert find_post_by_pid(posts, 1020)['post_number'] == 20
+    assert find_post_by_pid(posts, 999) is None
+    print("✓ Test 4 passed: Lookup by pid")
+    
+    print("\n✓ All tests passed!")
+
+
+if __name__ == '__main__':
+    test_merge_pages()
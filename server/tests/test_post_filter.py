@@ -0,0 +1,137 @@
+#!/usr/bin/env python3
+"""
+Tests for PostFilter matching.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from src.post_filter import PostFilter, thread_matches_post, validate_notification_rules, validate_post_filter
+
+
+def make_post(content='', author_uid=100, author_name='User1'):
+    return {
+        'pid': 1,
+        'tid': 12345,
+        'author_uid': author_uid,
+        'author_name': author_name,
+        'content': content,
+    }
+
+
+def test_empty_filter():
+    assert PostFilter().matches(make_post('anything'))
+    assert PostFilter.from_config(None) is None
+    assert PostFilter.from_config({}) is None
+    print("✓ Empty filter matches every post")
+
+
+def test_include_keywords():
+    f = PostFilter(include_keywords=['更新', 'patch'])
+    assert f.matches(make_post('今天更新了'))
+    assert f.matches(make_post('New PATCH notes'))
+    assert not f.matches(make_post('闲聊'))
+    print("✓ include_keywords")
+
+
+def test_exclude_keywords():
+    f = PostFilter(exclude_keywords=['广告'])
+    assert f.matches(make_post('正常回复'))
+    assert not f.matches(make_post('这是广告'))
+    print("✓ exclude_keywords")
+
+
+def test_keywords_ignore_bbcode():
+    f = PostFilter(include_keywords=['quote'])
+    assert not f.matches(make_post('[b]bold[/b]'))
+    assert not f.matches(make_post('[quote]quote text[/quote]reply'))
+    print("✓ Keywords match stripped content")
+
+
+def test_regex():
+    f = PostFilter(regex=r'v\d+\.\d+')
+    assert f.matches(make_post('Released V1.2'))
+    assert not f.matches(make_post('Released soon'))
+    assert not PostFilter(regex=r'v\d', case_sensitive=True).matches(make_post('V1'))
+    print("✓ regex")
+
+
+def test_author_uids():
+    f = PostFilter(author_uids=[100])
+    assert f.matches(make_post(author_uid=100))
+    assert not f.matches(make_post(author_uid=200))
+    print("✓ author_uids")
+
+
+def test_author_names():
+    f = PostFilter(author_names=['user1'])
+    assert f.matches(make_post(author_name='User1'))
+    assert not f.matches(make_post(author_name='User2'))
+    assert not PostFilter(author_names=['user1'], case_sensitive=True).matches(make_post(author_name='User1'))
+    print("✓ author_names")
+
+
+def test_case_sensitive():
+    f = PostFilter(include_keywords=['Patch'], case_sensitive=True)
+    assert f.matches(make_post('Patch notes'))
+    assert not f.matches(make_post('patch notes'))
+    print("✓ case_sensitive")
+
+
+def test_combined():
+    f = PostFilter.from_config({'include_keywords': ['更新'], 'author_uids': [100]})
+    assert f.matches(make_post('更新', author_uid=100))
+    assert not f.matches(make_post('更新', author_uid=200))
+    assert not f.matches(make_post('闲聊', author_uid=100))
+    print("✓ All criteria must match")
+
+
+def test_thread_matches_post():
+    post = make_post('版本更新', author_uid=100)
+    assert thread_matches_post(post, {100, 200}, None)
+    assert not thread_matches_post(post, {200}, None)
+    assert not thread_matches_post(post, set(), None)
+    assert thread_matches_post(post, {100}, PostFilter(include_keywords=['更新']))
+    assert not thread_matches_post(post, {100}, PostFilter(exclude_keywords=['版本']))
+    assert not thread_matches_post(post, {100}, PostFilter(regex='^闲聊'))
+    # The filter can't widen author_notification
+    assert not thread_matches_post(post, {200}, PostFilter(author_uids=[100]))
+    # author_exclusion wins over author_notification
+    assert not thread_matches_post(post, {100}, None, excluded_uids={100})
+    assert thread_matches_post(post, {100}, None, excluded_uids={300})
+    # notification_rules are ORed with author_notification
+    assert thread_matches_post(post, set(), None, rules=[{'type': 'author', 'uid': 100}])
+    assert thread_matches_post(post, {200}, None, rules=[{'type': 'keywords', 'keywords': ['[b]x', '更新']}])
+    assert not thread_matches_post(post, {200}, None, rules=[{'type': 'keywords', 'keywords': ['闲聊']}])
+    assert thread_matches_post(post, set(), None, rules=[{'type': 'any'}])
+    assert not thread_matches_post(post, set(), None, excluded_uids={100}, rules=[{'type': 'any'}])
+    assert not thread_matches_post(post, set(), PostFilter(regex='^闲聊'), rules=[{'type': 'any'}])
+    print("✓ thread_matches_post")
+
+
+def test_validate():
+    assert validate_post_filter({'include_keywords': ['a'], 'regex': 'a+'}, 'filter') == []
+    assert validate_post_filter({'regex': '('}, 'filter')
+    assert validate_post_filter({'author_uids': ['1']}, 'filter')
+    assert validate_post_filter({'case_sensitive': 'yes'}, 'filter')
+    assert validate_post_filter([], 'filter')
+    assert validate_notification_rules([{'type': 'author', 'uid': 1}, {'type': 'any'}], 'rules') == []
+    assert validate_notification_rules([{'type': 'keywords', 'keywords': []}], 'rules')
+    assert validate_notification_rules([{'type': 'author', 'uid': '1'}], 'rules')
+    assert validate_notification_rules([{'type': 'everything'}], 'rules')
+    print("✓ validate_post_filter")
+
+
+if __name__ == '__main__':
+    test_empty_filter()
+    test_include_keywords()
+    test_exclude_keywords()
+    test_keywords_ignore_bbcode()
+    test_regex()
+    test_author_uids()
+    test_author_names()
+    test_case_sensitive()
+    test_combined()
+    test_thread_matches_post()
+    test_validate()
+    print("\n✓ All tests passed!")
@@ -0,0 +1,88 @@
+#!/usr/bin/env python3
+"""
+Tests for notification schedule helpers.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+from datetime import datetime, time as dtime, timezone
+
+from src.schedule import expand_days, is_active, is_time_range, validate_schedule
+
+
+def test_is_time_range():
+    # Test 1: Normal range is half-open
+    assert is_time_range(dtime(9), dtime(18), dtime(9))
+    assert is_time_range(dtime(9), dtime(18), dtime(17, 59))
+    assert not is_time_range(dtime(9), dtime(18), dtime(18))
+    assert not is_time_range(dtime(9), dtime(18), dtime(8, 59))
+    print("✓ Test 1 passed: start <= now < end")
+    
+    # Test 2: Range wrapping past midnight
+    assert is_time_range(dtime(22), dtime(6), dtime(23))
+    assert is_time_range(dtime(22), dtime(6), dtime(0))
+    assert is_time_range(dtime(22), dtime(6), dtime(5, 59))
+    assert not is_time_range(dtime(22), dtime(6), dtime(6))
+    assert not is_time_range(dtime(22), dtime(6), dtime(12))
+    print("✓ Test 2 passed: Wrap-around range")
+    
+    # Test 3: Equal start and end cover the whole day
+    assert is_time_range(dtime(0), dtime(0), dtime(12))
+    assert is_time_range(dtime(8), dtime(8), dtime(7))
+    print("✓ Test 3 passed: Equal start and end")
+
+
+def test_expand_days():
+    # Test 4: Aliases
+    assert expand_days('weekdays') == {0, 1, 2, 3, 4}
+    assert expand_days('weekends') == {5, 6}
+    assert expand_days(None) == set(range(7))
+    assert expand_days('daily') == set(range(7))
+    print("✓ Test 4 passed: weekdays, weekends, daily")
+    
+    # Test 5: Names, ranges and lists
+    assert expand_days('mon-wed,sun') == {0, 1, 2, 6}
+    assert expand_days('fri-mon') == {4, 5, 6, 0}
+    assert expand_days(['Sat', 'weekdays']) == {0, 1, 2, 3, 4, 5}
+    print("✓ Test 5 passed: Names, ranges and lists")
+    
+    # Test 6: Unknown day names are rejected, and reported by validation
+    try:
+        expand_days(['mon', 'someday'])
+        assert False, "Expected ValueError"
+    except ValueError:
+        pass
+    errors = validate_schedule([{'days': ['mon', 'someday']}], 'bark_schedule')
+    assert len(errors) == 1 and errors[0].startswith('bark_schedule[0].days'), errors
+    print("✓ Test 6 passed: Invalid day names")
+
+
+def test_is_active():
+    # Test 7: Windows are matched against day and time
+    schedule = [{'days': 'weekdays', 'start': '22:00', 'end': '06:00'}]
+    assert is_active(schedule, datetime(2024, 1, 1, 23, 0))      # Monday night
+    assert not is_active(schedule, datetime(2024, 1, 6, 23, 0))  # Saturday night
+    assert not is_active(schedule, datetime(2024, 1, 1, 12, 0))
+    assert is_active([], datetime(2024, 1, 6, 12, 0))
+    print("✓ Test 7 passed: is_active")
+    
+    # Test 8: Windows are evaluated in their own timezone, then the default one
+    now = datetime(2024, 1, 1, 2, 0, tzinfo=timezone.utc)  # Monday 10:00 in Shanghai
+    shanghai = [{'start': '09:00', 'end': '11:00', 'timezone': 'Asia/Shanghai'}]
+    assert is_active(shanghai, now)
+    assert is_active(shanghai, now, default_timezone='America/New_York')
+    assert not is_active([{'start': '09:00', 'end': '11:00'}], now, default_timezone='America/New_York')
+    assert is_active([{'days': 'sun', 'start': '20:00', 'end': '22:00'}], now,
+                     default_timezone='America/New_York')  # Sunday 21:00 in New York
+    errors = validate_schedule([{'timezone': 'Mars/Olympus'}], 'bark_schedule')
+    assert errors and errors[0].startswith('bark_schedule[0].timezone'), errors
+    print("✓ Test 8 passed: Timezones")
+    
+    print("\n✓ All tests passed!")
+
+
+if __name__ == '__main__':
+    test_is_time_range()
+    test_expand_days()
+    test_is_active()
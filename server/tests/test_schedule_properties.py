@@ -0,0 +1,79 @@
+#!/usr/bin/env python3
+"""
+Randomized property tests for expand_days.
+
+Generates many day specifications from day-name fragments, separators,
+odd casing and non-ASCII text, and checks invariants that must hold for
+every input. Set SCHEDULE_PROPERTY_SEED to reproduce a failing run.
+
+Usage:
+    python tests/test_schedule_properties.py [iterations]
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import random
+
+from src.schedule import DAY_NAMES, expand_days
+
+FRAGMENTS = DAY_NAMES + ['weekdays', 'weekends', 'daily', '*', '-', ',', ' ', '',
+                         'MON', 'Fri', 'sUn', 'monday', 'mo', '周一', 'Ｍon', 'ſun', '\t', '\n']
+
+
+def random_token(rng):
+    return ''.join(rng.choice(FRAGMENTS) for _ in range(rng.randint(0, 4)))
+
+
+def random_spec(rng):
+    """A list of tokens or a comma-separated string of them."""
+    tokens = [random_token(rng) for _ in range(rng.randint(0, 5))]
+    return tokens if rng.random() < 0.5 else ','.join(tokens)
+
+
+def check_properties(spec):
+    try:
+        days = expand_days(spec)
+    except ValueError:
+        # Unknown day names are rejected; any other exception is a bug
+        return
+    assert isinstance(days, set), spec
+    assert days <= set(range(7)), (spec, days)
+
+
+def test_random_specs(iterations, seed):
+    rng = random.Random(seed)
+    for _ in range(iterations):
+        check_properties(random_spec(rng))
+    print(f"✓ Property 1 passed: {iterations} random specs, only valid weekdays or ValueError")
+
+
+def test_aliases(seed):
+    rng = random.Random(seed)
+    # 'weekdays' always gives the five business days, in any valid combination with itself
+    for _ in range(100):
+        spec = ['weekdays'] * rng.randint(1, 3)
+        assert expand_days(spec) == {0, 1, 2, 3, 4}
+        assert expand_days(','.join(' ' * rng.randint(0, 2) + s.upper() for s in spec)) == {0, 1, 2, 3, 4}
+    print("✓ Property 2 passed: weekdays is always Monday to Friday")
+    
+    # Each valid name adds exactly its own day
+    for _ in range(100):
+        names = rng.sample(DAY_NAMES, rng.randint(1, 7))
+        assert expand_days(names) == {DAY_NAMES.index(n) for n in names}
+    print("✓ Property 3 passed: Day names map to their weekday")
+
+
+def test_empty():
+    assert expand_days([]) == set()
+    print("✓ Property 4 passed: Empty list gives no days")
+
+
+if __name__ == '__main__':
+    iterations = int(sys.argv[1]) if len(sys.argv) > 1 else 5000
+    seed = int(os.environ.get('SCHEDULE_PROPERTY_SEED', random.randrange(2 ** 32)))
+    print(f"Seed: {seed}")
+    test_random_specs(iterations, seed)
+    test_aliases(seed)
+    test_empty()
+    print("\n✓ All tests passed!")
@@ -0,0 +1,115 @@
+#!/usr/bin/env python3
+"""
+Tests for snoozing threads, in the monitor and through the API.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import tempfile
+from datetime import datetime, timedelta
+
+from src.monitor import ThreadMonitor
+from tests.common import FIXTURE_TID, MockSender, config_fixture, write_config
+
+TID = FIXTURE_TID
+
+
+def make_monitor(directory: str) -> ThreadMonitor:
+    """Monitor with FIXTURE_TID in the database and check_thread recording the checked tids."""
+    config_path = write_config(config_fixture(), os.path.join(directory, 'config'))
+    monitor = ThreadMonitor(db_path=os.path.join(directory, 'data', 'nga.db'), config_path=config_path,
+                            senders=[MockSender()])
+    monitor.db.save_thread({'tid': TID, 'title': 'Snooze Thread', 'author_name': 'Author', 'author_uid': 100})
+    monitor.db.cursor.execute('INSERT INTO monitored_threads (tid, check_interval) VALUES (?, 60)', (TID,))
+    monitor.db.conn.commit()
+    
+    monitor.checked = []
+    
+    def check_thread(tid, **kwargs):
+        monitor.checked.append(tid)
+        return {'new_posts': 0}
+    
+    monitor.check_thread = check_thread
+    return monitor
+
+
+def make_directory() -> str:
+    directory = tempfile.mkdtemp()
+    os.makedirs(os.path.join(directory, 'config'))
+    os.makedirs(os.path.join(directory, 'data'))
+    return directory
+
+
+def test_monitor_snooze():
+    monitor = make_monitor(make_directory())
+    
+    try:
+        # Test 1: A snoozed thread is skipped by check_all
+        snooze_until = monitor.snooze_thread(TID, 3600)
+        assert monitor.is_snoozed(TID)
+        assert timedelta(seconds=3590) < snooze_until - datetime.now() <= timedelta(seconds=3600)
+        summary = monitor.check_all(verbose=False)
+        assert monitor.checked == [] and summary['checked'] == 0, summary
+        print("✓ Test 1 passed: Snoozed thread skipped")
+    
+        # Test 2: Once the snooze has ended the thread is checked again without any action
+        monitor.thread_states[TID].snooze_until = datetime.now() - timedelta(seconds=1)
+        assert not monitor.is_snoozed(TID)
+        summary = monitor.check_all(verbose=False)
+        assert monitor.checked == [TID] and summary['checked'] == 1, summary
+        print("✓ Test 2 passed: Thread resumes after the snooze expires")
+    
+        # Test 3: An expired snooze can't be cancelled
+        assert not monitor.cancel_snooze(TID)
+        print("✓ Test 3 passed: Expired snooze not cancellable")
+    finally:
+        monitor.close()
+
+
+def test_api_snooze():
+    # src.api reads config/config.json relative to the working directory
+    directory = make_directory()
+    cwd = os.getcwd()
+    os.chdir(directory)
+    monitor = make_monitor(directory)
+    from fastapi import HTTPException
+    import src.api as api
+    api.monitor = monitor
+    
+    try:
+        # Test 4: POST snoozes the thread and the status shows snooze_until
+        response = api.snooze_thread(TID, api.SnoozeRequest(duration_secs=3600), None)
+        assert response['status'] == 'snoozed' and response['tid'] == TID, response
+        assert api._thread_status(TID)['snooze_until'] == response['snooze_until']
+        monitor.check_all(verbose=False)
+        assert monitor.checked == []
+        print("✓ Test 4 passed: Thread snoozed through the API")
+    
+        # Test 5: DELETE ends the snooze and the thread is checked again
+        assert api.cancel_thread_snooze(TID, None) == {"status": "resumed", "tid": TID}
+        assert api._thread_status(TID)['snooze_until'] is None
+        monitor.check_all(verbose=False)
+        monitor.check_all(verbose=False)
+        assert monitor.checked == [TID, TID], monitor.checked
+        print("✓ Test 5 passed: Thread active again after DELETE")
+    
+        # Test 6: Cancelling without a snooze and snoozing an unmonitored thread are 404s
+        for call in (lambda: api.cancel_thread_snooze(TID, None),
+                     lambda: api.snooze_thread(TID + 1, api.SnoozeRequest(duration_secs=60), None)):
+            try:
+                call()
+                assert False, "Expected HTTPException"
+            except HTTPException as e:
+                assert e.status_code == 404, e.status_code
+        print("✓ Test 6 passed: 404 without a snooze or monitored thread")
+    finally:
+        api.monitor = None
+        monitor.close()
+        os.chdir(cwd)
+
+
+if __name__ == '__main__':
+    test_monitor_snooze()
+    test_api_snooze()
+    print("\n✓ All tests passed!")
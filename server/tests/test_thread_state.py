@@ -0,0 +1,99 @@
+#!/usr/bin/env python3
+"""
+Tests for the per-thread runtime state kept by the monitor.
+"""
+import sys
+import os
+sys.path.insert(0, os.path.dirname(os.path.dirname(os.path.abspath(__file__))))
+
+import tempfile
+from datetime import timedelta
+from typing import Optional
+
+from src.monitor import ThreadMonitor, next_check_time
+from tests.common import FIXTURE_TID, MockSender, config_fixture, write_config
+
+TID = FIXTURE_TID
+
+
+def make_monitor(sender: Optional[MockSender] = None, **overrides) -> ThreadMonitor:
+    tmp = tempfile.mkdtemp()
+    config_path = write_config(config_fixture(**overrides), tmp)
+    return ThreadMonitor(db_path=os.path.join(tmp, 'nga.db'), config_path=config_path,
+                         senders=[sender or MockSender()])
+
+
+def test_error_backoff():
+    monitor = make_monitor(error_backoff_factor=2.0, max_error_backoff_secs=1000)
+
+    try:
+        # Test 1: Each consecutive failure multiplies the interval by the factor
+        intervals = []
+        for _ in range(3):
+            state = monitor.update_thread_state(TID, {'error': 'timeout'}, 100)
+            intervals.append(state.effective_interval)
+        assert intervals == [200, 400, 800], intervals
+        assert state.consecutive_errors == 3 and state.last_error == 'timeout'
+        assert next_check_time(None, 100, state) == state.last_checked_at + timedelta(seconds=800)
+        print("✓ Test 1 passed: interval * factor ** errors")
+    
+        # Test 2: The backoff is capped at max_error_backoff_secs
+        state = monitor.update_thread_state(TID, {'error': 'timeout'}, 100)
+        assert state.effective_interval == 1000, state.effective_interval
+        state = monitor.update_thread_state(TID, {'error': 'timeout'}, 100)
+        assert state.effective_interval == 1000, state.effective_interval
+        print("✓ Test 2 passed: Backoff capped")
+    
+        # Test 3: A successful check restores the configured interval
+        state = monitor.update_thread_state(TID, {'new_posts': 0}, 100)
+        assert state.consecutive_errors == 0
+        assert state.effective_interval is None and state.last_error is None
+        state = monitor.update_thread_state(TID, {'error': 'timeout'}, 100)
+        assert state.effective_interval == 200, state.effective_interval
+        print("✓ Test 3 passed: Backoff reset after a success")
+    finally:
+        monitor.close()
+
+
+def test_failure_alert():
+    sender = MockSender()
+    monitor = make_monitor(sender, error_alert_threshold=3, error_alert_repeat_hours=6)
+    
+    try:
+        # Test 4: No alert below the threshold, one when it is reached
+        for _ in range(2):
+            monitor.update_thread_state(TID, {'error': 'timeout'}, 60)
+        assert sender.calls() == []
+        state = monitor.update_thread_state(TID, {'error': 'timeout'}, 60)
+        assert len(sender.calls()) == 1 and state.last_error_alert_at is not None
+        assert str(TID) in sender.calls()[0][1] and 'timeout' in sender.calls()[0][1]
+        print("✓ Test 4 passed: Alert when the threshold is crossed")
+        
+        # Test 5: A thread that keeps failing doesn't alert again, even after the repeat window
+        state.last_error_alert_at -= timedelta(hours=7)
+        for _ in range(5):
+            monitor.update_thread_state(TID, {'error': 'timeout'}, 60)
+        assert len(sender.calls()) == 1, sender.calls()
+        print("✓ Test 5 passed: One alert per failure streak")
+        
+        # Test 6: After recovering, a new streak alerts again once the repeat window has passed
+        monitor.update_thread_state(TID, {'new_posts': 0}, 60)
+        for _ in range(3):
+            monitor.update_thread_state(TID, {'error': 'timeout'}, 60)
+        assert len(sender.calls()) == 2, sender.calls()
+        print("✓ Test 6 passed: Alert again after the thread recovered")
+        
+        # Test 7: Within the repeat window a new streak stays silent
+        monitor.update_thread_state(TID, {'new_posts': 0}, 60)
+        for _ in range(3):
+            monitor.update_thread_state(TID, {'error': 'timeout'}, 60)
+        assert len(sender.calls()) == 2, sender.calls()
+        print("✓ Test 7 passed: Flapping thread throttled to error_alert_repeat_hours")
+    finally:
+        monitor.close()
+
+
+if __name__ == '__main__':
+    test_error_backoff()
+    test_failure_alert()
+    print("\n✓ All tests passed!")